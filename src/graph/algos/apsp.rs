@@ -1,4 +1,4 @@
-use crate::graph::Graph;
+use crate::graph::{csr::Csr, Graph};
 
 /// every algo need return the result's ownership to js, so we need to return a Vec<f32>
 pub fn run(graph: &mut Graph) -> Vec<f32> {
@@ -15,22 +15,18 @@ pub fn run(graph: &mut Graph) -> Vec<f32> {
 }
 
 pub fn make_floyd_warshall(graph: &mut Graph) -> Vec<Vec<f32>> {
-    let len = graph.vertexes.len();
+    make_csr_floyd_warshall(&graph.snapshot())
+}
+
+pub fn make_csr_floyd_warshall(csr: &Csr) -> Vec<Vec<f32>> {
+    let len = csr.len();
     let mut dist = vec![vec![f32::INFINITY; len]; len];
-    for i in 0..len {
-        dist[i] = vec![f32::INFINITY; len];
-        let neighbors = graph.neighbors_map.get_mut(i).unwrap();
-        for j in 0..len {
-            if i == j {
-                dist[i][j] = 0.;
-            } else if neighbors.contains_key(&j) {
-                dist[i][j] = neighbors[&j];
-            } else {
-                dist[i][j] = f32::INFINITY;
-            }
+    for (i, row) in dist.iter_mut().enumerate() {
+        for (j, weight) in csr.out_edges(i) {
+            row[j] = weight;
         }
+        row[i] = 0.;
     }
-    let len = graph.vertexes.len();
     for k in 0..len {
         for i in 0..len {
             for j in 0..len {
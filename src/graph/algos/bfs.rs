@@ -1,27 +1,32 @@
-use crate::graph::Graph;
-use std::collections::{HashSet, VecDeque};
+use crate::graph::{csr::Csr, Graph};
+use std::collections::VecDeque;
 use wasm_bindgen::prelude::*;
 
-#[wasm_bindgen]
-pub fn run(graph: &Graph, root: &str) -> Vec<f32> {
-    let mut queue = VecDeque::new();
-    let mut visited = HashSet::new();
-    let root_index = graph.get_vertex_by_id(root);
-    match root_index {
-        Some(index) => {
-            queue.push_back(index);
-        }
-        None => {
-            return vec![];
-        }
-    }
+/// vertexes reachable from `root` in bfs order
+pub fn make_bfs(csr: &Csr, root: usize) -> Vec<usize> {
+    let mut visited = vec![false; csr.len()];
+    let mut order = vec![];
+    let mut queue = VecDeque::from(vec![root]);
+    visited[root] = true;
     while let Some(src) = queue.pop_front() {
-        visited.insert(src);
-        graph.get_neighbors(src).iter().for_each(|dst| {
-            if !visited.contains(dst) {
+        order.push(src);
+        for dst in csr.out_neighbors(src) {
+            if !visited[*dst] {
+                visited[*dst] = true;
                 queue.push_back(*dst);
             }
-        });
+        }
+    }
+    order
+}
+
+#[wasm_bindgen]
+pub fn run(graph: &Graph, root: &str) -> Vec<f32> {
+    match graph.get_vertex_by_id(root) {
+        Some(index) => make_bfs(&graph.snapshot(), index)
+            .iter()
+            .map(|v| *v as f32)
+            .collect(),
+        None => vec![],
     }
-    visited.iter().map(|&v| v as f32).collect()
 }
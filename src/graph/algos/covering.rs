@@ -0,0 +1,109 @@
+use crate::graph::Graph;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use wasm_bindgen::prelude::*;
+
+/// greedy ln(n)-approximation: keep taking the vertex that dominates most undominated vertexes
+pub fn make_dominating_set(graph: &Graph) -> Vec<usize> {
+    let neighbors = graph.snapshot().undirected();
+    let len = neighbors.len();
+    let mut dominated = vec![false; len];
+    let gain = |v: usize, dominated: &[bool]| {
+        (!dominated[v]) as usize
+            + neighbors
+                .out_neighbors(v)
+                .iter()
+                .filter(|u| !dominated[**u])
+                .count()
+    };
+    // gains only shrink, so a popped entry whose gain is still current is the best choice
+    let mut heap: BinaryHeap<(usize, Reverse<usize>)> = (0..len)
+        .map(|v| (neighbors.out_neighbors(v).len() + 1, Reverse(v)))
+        .collect();
+    let mut result = vec![];
+    while let Some((stored, Reverse(v))) = heap.pop() {
+        let now = gain(v, &dominated);
+        if now == 0 {
+            continue;
+        }
+        if now < stored {
+            heap.push((now, Reverse(v)));
+            continue;
+        }
+        result.push(v);
+        dominated[v] = true;
+        for u in neighbors.out_neighbors(v) {
+            dominated[*u] = true;
+        }
+    }
+    result.sort_unstable();
+    result
+}
+
+/// both ends of a maximal matching, at most twice the minimum vertex cover
+pub fn make_vertex_cover(graph: &Graph) -> Vec<usize> {
+    let mut covered = vec![false; graph.vertexes.len()];
+    for edge in &graph.edges {
+        if !covered[edge.source] && !covered[edge.target] {
+            covered[edge.source] = true;
+            covered[edge.target] = true;
+        }
+    }
+    (0..covered.len()).filter(|v| covered[*v]).collect()
+}
+
+/// greedy maximal independent set that always takes the vertex with the fewest remaining neighbors
+pub fn make_independent_set(graph: &Graph) -> Vec<usize> {
+    let neighbors = graph.snapshot().undirected();
+    let len = neighbors.len();
+    let mut removed = vec![false; len];
+    let mut degree: Vec<usize> = (0..neighbors.len())
+        .map(|v| neighbors.out_neighbors(v).len())
+        .collect();
+    let mut heap: BinaryHeap<Reverse<(usize, usize)>> =
+        (0..len).map(|v| Reverse((degree[v], v))).collect();
+    let mut result = vec![];
+    while let Some(Reverse((d, v))) = heap.pop() {
+        if removed[v] || d != degree[v] {
+            continue;
+        }
+        result.push(v);
+        removed[v] = true;
+        for u in neighbors.out_neighbors(v) {
+            if removed[*u] {
+                continue;
+            }
+            removed[*u] = true;
+            for w in neighbors.out_neighbors(*u) {
+                if !removed[*w] {
+                    degree[*w] -= 1;
+                    heap.push(Reverse((degree[*w], *w)));
+                }
+            }
+        }
+    }
+    result.sort_unstable();
+    result
+}
+
+fn to_ids(graph: &Graph, vertexes: Vec<usize>) -> Vec<String> {
+    vertexes
+        .into_iter()
+        .map(|v| graph.vertexes[v].id.clone())
+        .collect()
+}
+
+#[wasm_bindgen]
+pub fn dominating_set(graph: &Graph) -> Vec<String> {
+    to_ids(graph, make_dominating_set(graph))
+}
+
+#[wasm_bindgen]
+pub fn vertex_cover(graph: &Graph) -> Vec<String> {
+    to_ids(graph, make_vertex_cover(graph))
+}
+
+#[wasm_bindgen]
+pub fn independent_set(graph: &Graph) -> Vec<String> {
+    to_ids(graph, make_independent_set(graph))
+}
@@ -0,0 +1,290 @@
+use crate::graph::{csr::Csr, Graph};
+use std::collections::{HashSet, VecDeque};
+use wasm_bindgen::prelude::*;
+
+/// cycles packed one after another, cycle i is `vertexes[offsets[i]..offsets[i + 1]]`
+#[wasm_bindgen]
+pub struct CycleList {
+    vertexes: Vec<u32>,
+    offsets: Vec<u32>,
+}
+
+impl CycleList {
+    fn new(cycles: &[Vec<usize>]) -> CycleList {
+        let mut offsets = vec![0];
+        let mut vertexes = vec![];
+        for cycle in cycles {
+            vertexes.extend(cycle.iter().map(|v| *v as u32));
+            offsets.push(vertexes.len() as u32);
+        }
+        CycleList { vertexes, offsets }
+    }
+}
+
+#[wasm_bindgen]
+impl CycleList {
+    pub fn count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn vertexes(&self) -> Vec<u32> {
+        self.vertexes.clone()
+    }
+
+    /// count + 1 items
+    pub fn offsets(&self) -> Vec<u32> {
+        self.offsets.clone()
+    }
+
+    pub fn get(&self, index: usize) -> Vec<u32> {
+        self.vertexes[self.offsets[index] as usize..self.offsets[index + 1] as usize].to_vec()
+    }
+}
+
+/// fundamental cycles of a bfs spanning forest of the undirected view, one cycle per non tree
+/// edge so there are m - n + c of them (self loops included). every cycle starts at the tree
+/// root side and follows the cycle order
+pub fn make_cycle_basis(graph: &Graph) -> Vec<Vec<usize>> {
+    let len = graph.vertexes.len();
+    let mut neighbors = vec![vec![]; len];
+    for edge in &graph.edges {
+        neighbors[edge.source].push(edge.target);
+        if edge.source != edge.target {
+            neighbors[edge.target].push(edge.source);
+        }
+    }
+    let mut parent = vec![usize::MAX; len];
+    let mut depth = vec![0; len];
+    let mut visited = vec![false; len];
+    for root in 0..len {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut queue = VecDeque::from(vec![root]);
+        while let Some(v) = queue.pop_front() {
+            for u in &neighbors[v] {
+                if !visited[*u] {
+                    visited[*u] = true;
+                    parent[*u] = v;
+                    depth[*u] = depth[v] + 1;
+                    queue.push_back(*u);
+                }
+            }
+        }
+    }
+    let mut cycles = vec![];
+    // the edge to the parent is used once by the tree, any other copy closes a cycle
+    let mut tree_edge_used = vec![false; len];
+    for edge in &graph.edges {
+        let (a, b) = (edge.source, edge.target);
+        if a == b {
+            cycles.push(vec![a]);
+            continue;
+        }
+        if parent[b] == a && !tree_edge_used[b] {
+            tree_edge_used[b] = true;
+            continue;
+        }
+        if parent[a] == b && !tree_edge_used[a] {
+            tree_edge_used[a] = true;
+            continue;
+        }
+        // walk both ends up to their lowest common ancestor
+        let (mut x, mut y) = (a, b);
+        let (mut left, mut right) = (vec![], vec![]);
+        while x != y {
+            if depth[x] >= depth[y] {
+                left.push(x);
+                x = parent[x];
+            } else {
+                right.push(y);
+                y = parent[y];
+            }
+        }
+        let mut cycle = vec![x];
+        cycle.extend(right.into_iter().rev());
+        cycle.extend(left);
+        cycles.push(cycle);
+    }
+    cycles
+}
+
+/// the strongly connected component of `start` inside the subgraph of the `allowed` vertexes
+fn make_local_component(csr: &Csr, start: usize, allowed: &[bool]) -> Vec<bool> {
+    let len = csr.len();
+    let reach = |backward: bool| {
+        let mut seen = vec![false; len];
+        seen[start] = true;
+        let mut stack = vec![start];
+        while let Some(v) = stack.pop() {
+            let row = if backward {
+                csr.in_neighbors(v)
+            } else {
+                csr.out_neighbors(v)
+            };
+            for u in row {
+                if allowed[*u] && !seen[*u] {
+                    seen[*u] = true;
+                    stack.push(*u);
+                }
+            }
+        }
+        seen
+    };
+    let forward = reach(false);
+    let backward = reach(true);
+    (0..len).map(|v| forward[v] && backward[v]).collect()
+}
+
+struct Circuits<'a> {
+    csr: &'a Csr,
+    max_length: usize,
+    max_count: usize,
+    blocked: Vec<bool>,
+    blocked_by: Vec<HashSet<usize>>,
+    cycles: Vec<Vec<usize>>,
+}
+
+impl Circuits<'_> {
+    fn unblock(&mut self, v: usize) {
+        let mut stack = vec![v];
+        while let Some(x) = stack.pop() {
+            if self.blocked[x] {
+                self.blocked[x] = false;
+                stack.extend(self.blocked_by[x].drain());
+            }
+        }
+    }
+
+    /// johnson's circuit search from `start` inside `allowed`, iterative so long cycles can't
+    /// overflow the stack. hitting the length limit counts as finding a cycle, that only skips
+    /// the blocking optimization and keeps the search complete
+    fn search(&mut self, start: usize, allowed: &[bool], accept: &dyn Fn(&[usize]) -> bool) {
+        for (v, _) in allowed.iter().enumerate().filter(|(_, a)| **a) {
+            self.blocked[v] = false;
+            self.blocked_by[v].clear();
+        }
+        let mut path = vec![start];
+        self.blocked[start] = true;
+        // (vertex, position of the next neighbor, a cycle was closed below)
+        let mut frames = vec![(start, 0, false)];
+        while let Some(frame) = frames.last_mut() {
+            let (v, pos) = (frame.0, frame.1);
+            if let Some(w) = self.csr.out_neighbors(v).get(pos).copied() {
+                frame.1 += 1;
+                if !allowed[w] {
+                    continue;
+                }
+                if w == start {
+                    frame.2 = true;
+                    if accept(&path) {
+                        self.cycles.push(path.clone());
+                        if self.cycles.len() >= self.max_count {
+                            return;
+                        }
+                    }
+                } else if !self.blocked[w] {
+                    if path.len() < self.max_length {
+                        path.push(w);
+                        self.blocked[w] = true;
+                        frames.push((w, 0, false));
+                    } else {
+                        frame.2 = true;
+                    }
+                }
+                continue;
+            }
+            let (_, _, found) = frames.pop().unwrap();
+            if found {
+                self.unblock(v);
+            } else {
+                for w in self.csr.out_neighbors(v) {
+                    if allowed[*w] {
+                        self.blocked_by[*w].insert(v);
+                    }
+                }
+            }
+            path.pop();
+            if let Some(parent) = frames.last_mut() {
+                parent.2 |= found;
+            }
+        }
+    }
+}
+
+/// elementary circuits by johnson's algorithm, every cycle starts at its smallest vertex, or at
+/// `through` when it is given and then only the cycles through that vertex are listed.
+/// cycles have at most `max_length` vertexes and at most `max_count` of them are returned.
+/// an undirected graph lists every cycle of 3 or more vertexes once, plus its self loops
+pub fn make_simple_cycles(
+    graph: &Graph,
+    max_length: usize,
+    max_count: usize,
+    through: Option<usize>,
+) -> Vec<Vec<usize>> {
+    if max_length == 0 || max_count == 0 {
+        return vec![];
+    }
+    let len = graph.vertexes.len();
+    let csr = graph.snapshot();
+    let mut circuits = Circuits {
+        csr: &csr,
+        max_length,
+        max_count,
+        blocked: vec![false; len],
+        blocked_by: vec![HashSet::new(); len],
+        cycles: vec![],
+    };
+    let directed = graph.is_directed;
+    // an undirected cycle is found in both directions and every edge looks like a 2-cycle
+    let accept = move |path: &[usize]| {
+        directed || path.len() == 1 || (path.len() > 2 && path[1] < path[path.len() - 1])
+    };
+    match through {
+        Some(start) => {
+            let component = make_local_component(&csr, start, &vec![true; len]);
+            circuits.search(start, &component, &accept);
+        }
+        None => {
+            for start in 0..len {
+                let above: Vec<bool> = (0..len).map(|v| v >= start).collect();
+                let component = make_local_component(&csr, start, &above);
+                circuits.search(start, &component, &accept);
+                if circuits.cycles.len() >= max_count {
+                    break;
+                }
+            }
+        }
+    }
+    circuits.cycles
+}
+
+#[wasm_bindgen]
+pub fn cycle_basis(graph: &Graph) -> CycleList {
+    CycleList::new(&make_cycle_basis(graph))
+}
+
+/// `max_length` and `max_count` of 0 mean no limit, `through` is a vertex id
+#[wasm_bindgen]
+pub fn simple_cycles(
+    graph: &Graph,
+    max_length: usize,
+    max_count: usize,
+    through: Option<String>,
+) -> CycleList {
+    let through = match through {
+        Some(id) => match graph.vertexs_map.get(&id) {
+            Some(index) => Some(*index),
+            None => return CycleList::new(&[]),
+        },
+        None => None,
+    };
+    let limit = |v: usize| if v == 0 { usize::MAX } else { v };
+    CycleList::new(&make_simple_cycles(
+        graph,
+        limit(max_length),
+        limit(max_count),
+        through,
+    ))
+}
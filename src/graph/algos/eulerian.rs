@@ -0,0 +1,135 @@
+use crate::graph::{utils::disjoint_set::DisjointSet, Graph};
+use wasm_bindgen::prelude::*;
+
+/// every edge lies in one (weakly) connected component, isolated vertexes don't matter
+fn edges_connected(graph: &Graph) -> bool {
+    let mut set = DisjointSet::new(graph.vertexes.len());
+    for edge in &graph.edges {
+        set.union(edge.source, edge.target);
+    }
+    let mut roots = graph.edges.iter().map(|edge| set.find(edge.source));
+    match roots.next() {
+        Some(first) => roots.all(|root| root == first),
+        None => true,
+    }
+}
+
+/// where an eulerian trail has to start, `circuit` asks for a closed trail.
+/// return None when there is no such trail or no vertex to start from
+pub fn make_eulerian_start(graph: &Graph, circuit: bool) -> Option<usize> {
+    if graph.vertexes.is_empty() || !edges_connected(graph) {
+        return None;
+    }
+    let len = graph.vertexes.len();
+    // out - in for directed graphs, degree parity for undirected ones
+    let mut balance = vec![0i64; len];
+    for edge in &graph.edges {
+        if graph.is_directed {
+            balance[edge.source] += 1;
+            balance[edge.target] -= 1;
+        } else {
+            balance[edge.source] += 1;
+            balance[edge.target] += 1;
+        }
+    }
+    let first_edge_vertex = graph.edges.first().map(|edge| edge.source).unwrap_or(0);
+    let odd: Vec<usize> = if graph.is_directed {
+        (0..len).filter(|v| balance[*v] != 0).collect()
+    } else {
+        (0..len).filter(|v| balance[*v] % 2 != 0).collect()
+    };
+    if odd.is_empty() {
+        return Some(first_edge_vertex);
+    }
+    if circuit || odd.len() != 2 {
+        return None;
+    }
+    if !graph.is_directed {
+        return Some(odd[0]);
+    }
+    let (a, b) = (odd[0], odd[1]);
+    match (balance[a], balance[b]) {
+        (1, -1) => Some(a),
+        (-1, 1) => Some(b),
+        _ => None,
+    }
+}
+
+pub fn has_eulerian_circuit(graph: &Graph) -> bool {
+    make_eulerian_start(graph, true).is_some()
+}
+
+pub fn has_eulerian_path(graph: &Graph) -> bool {
+    make_eulerian_start(graph, false).is_some()
+}
+
+/// hierholzer's algorithm, return the indexes of `graph.edges` in walking order.
+/// `circuit` asks for a closed trail, otherwise an open path is returned when there is no circuit
+pub fn make_eulerian_trail(graph: &Graph, circuit: bool) -> Option<Vec<usize>> {
+    let start = make_eulerian_start(graph, circuit)?;
+    let len = graph.vertexes.len();
+    // (edge index, other end) in edge order so the result is deterministic
+    let mut incident: Vec<Vec<(usize, usize)>> = vec![vec![]; len];
+    for (index, edge) in graph.edges.iter().enumerate() {
+        incident[edge.source].push((index, edge.target));
+        if !graph.is_directed && edge.source != edge.target {
+            incident[edge.target].push((index, edge.source));
+        }
+    }
+    let mut used = vec![false; graph.edges.len()];
+    let mut next = vec![0; len];
+    let mut trail = Vec::with_capacity(graph.edges.len());
+    // (vertex, edge that reached it)
+    let mut stack = vec![(start, usize::MAX)];
+    while let Some((v, arrived_by)) = stack.last().copied() {
+        while next[v] < incident[v].len() && used[incident[v][next[v]].0] {
+            next[v] += 1;
+        }
+        match incident[v].get(next[v]) {
+            Some((edge, other)) => {
+                used[*edge] = true;
+                stack.push((*other, *edge));
+            }
+            None => {
+                stack.pop();
+                if arrived_by != usize::MAX {
+                    trail.push(arrived_by);
+                }
+            }
+        }
+    }
+    trail.reverse();
+    Some(trail)
+}
+
+#[wasm_bindgen]
+pub fn is_eulerian(graph: &Graph) -> bool {
+    has_eulerian_circuit(graph)
+}
+
+#[wasm_bindgen]
+pub fn is_semi_eulerian(graph: &Graph) -> bool {
+    has_eulerian_path(graph)
+}
+
+/// indexes of the edges in adding order, listed in walking order.
+/// empty when the graph has no eulerian circuit
+#[wasm_bindgen]
+pub fn eulerian_circuit(graph: &Graph) -> Vec<u32> {
+    make_eulerian_trail(graph, true)
+        .unwrap_or_default()
+        .iter()
+        .map(|e| *e as u32)
+        .collect()
+}
+
+/// like `eulerian_circuit`, but an open path is returned when there is no circuit.
+/// empty when there is no eulerian path
+#[wasm_bindgen]
+pub fn eulerian_path(graph: &Graph) -> Vec<u32> {
+    make_eulerian_trail(graph, false)
+        .unwrap_or_default()
+        .iter()
+        .map(|e| *e as u32)
+        .collect()
+}
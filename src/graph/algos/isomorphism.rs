@@ -0,0 +1,173 @@
+use crate::graph::{
+    algos::vf2::{find_matches, MatchMode},
+    Graph,
+};
+use wasm_bindgen::prelude::*;
+
+/// fnv-1a, unlike the std hasher its output is fixed so hashes can be stored and compared later
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Fnv {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+}
+
+fn sorted_labels<'a>(labels: &[u64], neighbors: impl Iterator<Item = &'a usize>) -> Vec<u64> {
+    let mut list: Vec<u64> = neighbors.map(|u| labels[*u]).collect();
+    list.sort_unstable();
+    list
+}
+
+/// weisfeiler-lehman colors of every vertex after each round, round 0 is the vertex label or the
+/// degree, vertexes past the end of `labels` get the empty label. directed graphs look at out
+/// and in neighbors separately, edge weights are ignored
+pub fn make_wl_colors(
+    graph: &Graph,
+    labels: Option<&[String]>,
+    iterations: usize,
+) -> Vec<Vec<u64>> {
+    let len = graph.vertexes.len();
+    let csr = graph.snapshot();
+    let initial: Vec<u64> = (0..len)
+        .map(|v| {
+            let mut hasher = Fnv::new();
+            match labels {
+                Some(labels) => hasher.write(labels.get(v).map_or(&b""[..], |l| l.as_bytes())),
+                None => {
+                    hasher.write_u64(csr.out_degree(v) as u64);
+                    hasher.write_u64(if graph.is_directed {
+                        csr.in_degree(v) as u64
+                    } else {
+                        0
+                    });
+                }
+            }
+            hasher.0
+        })
+        .collect();
+    let mut rounds = vec![initial];
+    for _ in 0..iterations {
+        let last = rounds.last().unwrap();
+        let next = (0..len)
+            .map(|v| {
+                let mut hasher = Fnv::new();
+                hasher.write_u64(last[v]);
+                for label in sorted_labels(last, csr.out_neighbors(v).iter()) {
+                    hasher.write_u64(label);
+                }
+                if graph.is_directed {
+                    hasher.write(b"<");
+                    for label in sorted_labels(last, csr.in_neighbors(v).iter()) {
+                        hasher.write_u64(label);
+                    }
+                }
+                hasher.0
+            })
+            .collect();
+        rounds.push(next);
+    }
+    rounds
+}
+
+/// hash of the sorted color histograms of all rounds, isomorphic graphs always get the same
+/// hash, different hashes prove the graphs differ
+pub fn make_wl_hash(graph: &Graph, labels: Option<&[String]>, iterations: usize) -> String {
+    let mut hasher = Fnv::new();
+    hasher.write(if graph.is_directed { b"d" } else { b"u" });
+    for mut colors in make_wl_colors(graph, labels, iterations) {
+        colors.sort_unstable();
+        for color in colors {
+            hasher.write_u64(color);
+        }
+    }
+    format!("{:016x}", hasher.0)
+}
+
+fn arcs_len(graph: &Graph) -> usize {
+    graph.neighbors_map.iter().map(|map| map.len()).sum()
+}
+
+/// exact check by vf2, return `mapping[a_index] = b_index` or None.
+/// vertexes are only paired when their final wl colors agree, which prunes most of the search,
+/// still exponential in the worst case so it is meant for small graphs
+pub fn make_isomorphism(
+    a: &Graph,
+    b: &Graph,
+    a_labels: Option<&[String]>,
+    b_labels: Option<&[String]>,
+) -> Option<Vec<usize>> {
+    if a.is_directed != b.is_directed
+        || a.vertexes.len() != b.vertexes.len()
+        || arcs_len(a) != arcs_len(b)
+        || a_labels.is_some() != b_labels.is_some()
+    {
+        return None;
+    }
+    if a.vertexes.is_empty() {
+        return Some(vec![]);
+    }
+    let iterations = 3;
+    let a_colors = make_wl_colors(a, a_labels, iterations).pop().unwrap();
+    let b_colors = make_wl_colors(b, b_labels, iterations).pop().unwrap();
+    let (mut a_sorted, mut b_sorted) = (a_colors.clone(), b_colors.clone());
+    a_sorted.sort_unstable();
+    b_sorted.sort_unstable();
+    if a_sorted != b_sorted {
+        return None;
+    }
+    find_matches(
+        a,
+        b,
+        MatchMode::Isomorphism,
+        1,
+        |u, v| a_colors[u] == b_colors[v],
+        |_, _| true,
+    )
+    .pop()
+}
+
+/// `labels` gives one label per vertex in index order
+#[wasm_bindgen]
+pub fn wl_hash(graph: &Graph, iterations: usize, labels: Option<Vec<String>>) -> String {
+    make_wl_hash(graph, labels.as_deref(), iterations)
+}
+
+/// return the mapping as a flat array of [a_id, b_id, ...], empty when the graphs differ
+#[wasm_bindgen]
+pub fn isomorphism_mapping(
+    a: &Graph,
+    b: &Graph,
+    a_labels: Option<Vec<String>>,
+    b_labels: Option<Vec<String>>,
+) -> Vec<String> {
+    match make_isomorphism(a, b, a_labels.as_deref(), b_labels.as_deref()) {
+        Some(mapping) => mapping
+            .iter()
+            .enumerate()
+            .flat_map(|(u, v)| [a.vertexes[u].id.clone(), b.vertexes[*v].id.clone()])
+            .collect(),
+        None => vec![],
+    }
+}
+
+#[wasm_bindgen]
+pub fn is_isomorphic(
+    a: &Graph,
+    b: &Graph,
+    a_labels: Option<Vec<String>>,
+    b_labels: Option<Vec<String>>,
+) -> bool {
+    make_isomorphism(a, b, a_labels.as_deref(), b_labels.as_deref()).is_some()
+}
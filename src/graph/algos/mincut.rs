@@ -0,0 +1,122 @@
+use crate::graph::Graph;
+use std::collections::{BinaryHeap, HashMap};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct MinCut {
+    pub weight: f32,
+    side: Vec<usize>,
+}
+
+#[wasm_bindgen]
+impl MinCut {
+    /// vertexes on one side of the cut, the rest of the graph is the other side
+    pub fn side(&self) -> Vec<u32> {
+        self.side.iter().map(|v| *v as u32).collect()
+    }
+}
+
+/// |weight| of every edge summed per vertex pair, both directions of a directed graph count
+pub fn make_undirected_weights(graph: &Graph) -> Vec<HashMap<usize, f64>> {
+    let mut adjacency = vec![HashMap::new(); graph.vertexes.len()];
+    for edge in &graph.edges {
+        if edge.source == edge.target {
+            continue;
+        }
+        let w = edge.weight.abs() as f64;
+        *adjacency[edge.source].entry(edge.target).or_insert(0.) += w;
+        *adjacency[edge.target].entry(edge.source).or_insert(0.) += w;
+    }
+    adjacency
+}
+
+/// heap entry ordered by weight, ties go to the smaller vertex
+#[derive(PartialEq)]
+pub struct Candidate(pub f64, pub usize);
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0
+            .total_cmp(&other.0)
+            .then_with(|| other.1.cmp(&self.1))
+    }
+}
+
+/// stoer-wagner global minimum cut of the undirected view of the graph
+pub fn make_min_cut(graph: &Graph) -> MinCut {
+    let len = graph.vertexes.len();
+    if len < 2 {
+        return MinCut {
+            weight: 0.,
+            side: (0..len).collect(),
+        };
+    }
+    let mut adjacency = make_undirected_weights(graph);
+    let mut groups: Vec<Vec<usize>> = (0..len).map(|v| vec![v]).collect();
+    let mut active = vec![true; len];
+    let mut best = (f64::INFINITY, vec![]);
+    for phase in 0..(len - 1) {
+        // maximum adjacency search over the remaining merged vertexes
+        let mut weight = vec![0.; len];
+        let mut added = vec![false; len];
+        let mut heap: BinaryHeap<Candidate> = (0..len)
+            .filter(|v| active[*v])
+            .map(|v| Candidate(0., v))
+            .collect();
+        let (mut s, mut t) = (usize::MAX, usize::MAX);
+        let remaining = len - phase;
+        let mut count = 0;
+        while let Some(Candidate(w, v)) = heap.pop() {
+            if added[v] || w < weight[v] {
+                continue;
+            }
+            added[v] = true;
+            s = t;
+            t = v;
+            count += 1;
+            if count == remaining {
+                break;
+            }
+            for (u, uw) in &adjacency[v] {
+                if !added[*u] {
+                    weight[*u] += uw;
+                    heap.push(Candidate(weight[*u], *u));
+                }
+            }
+        }
+        if weight[t] < best.0 {
+            best = (weight[t], groups[t].clone());
+        }
+        // merge t into s
+        let moved = std::mem::take(&mut groups[t]);
+        groups[s].extend(moved);
+        let edges: Vec<(usize, f64)> = adjacency[t].drain().collect();
+        for (u, w) in edges {
+            adjacency[u].remove(&t);
+            if u != s {
+                *adjacency[s].entry(u).or_insert(0.) += w;
+                *adjacency[u].entry(s).or_insert(0.) += w;
+            }
+        }
+        active[t] = false;
+    }
+    let mut side = best.1;
+    side.sort_unstable();
+    MinCut {
+        weight: best.0 as f32,
+        side,
+    }
+}
+
+#[wasm_bindgen]
+pub fn min_cut(graph: &Graph) -> MinCut {
+    make_min_cut(graph)
+}
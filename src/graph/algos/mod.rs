@@ -1,5 +1,24 @@
 pub mod apsp;
 pub mod bfs;
 pub mod cnc;
+pub mod covering;
+pub mod cycles;
+pub mod eulerian;
+pub mod isomorphism;
+pub mod mincut;
+pub mod motif;
 pub mod nforce;
+pub mod partition;
+pub mod random_walk;
+pub mod reachability;
+pub mod rwr;
+pub mod scc;
+pub mod similarity;
+pub mod simrank;
+pub mod spectral;
 pub mod sssp;
+pub mod stats;
+pub mod steiner;
+pub mod transform;
+pub mod tsp;
+pub mod vf2;
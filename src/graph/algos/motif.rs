@@ -0,0 +1,288 @@
+use crate::graph::{csr::Csr, Graph};
+use std::collections::BTreeMap;
+use wasm_bindgen::prelude::*;
+
+pub const UNDIRECTED_MOTIFS: [&str; 8] = [
+    "wedge", "triangle", "path4", "star4", "cycle4", "paw", "diamond", "clique4",
+];
+
+/// holland-leinhardt triad types, `make_triad_census` counts them in this order
+pub const TRIAD_NAMES: [&str; 16] = [
+    "003", "012", "102", "021D", "021U", "021C", "111D", "111U", "030T", "030C", "201", "120D",
+    "120U", "120C", "210", "300",
+];
+
+/// triad code (see `tricode`) to 1 based index of `TRIAD_NAMES`, from batagelj and mrvar
+const TRICODES: [usize; 64] = [
+    1, 2, 2, 3, 2, 4, 6, 8, 2, 6, 5, 7, 3, 8, 7, 11, 2, 6, 4, 8, 5, 9, 9, 13, 6, 10, 9, 14, 7, 14,
+    12, 15, 2, 5, 6, 7, 6, 9, 10, 14, 4, 9, 9, 12, 8, 13, 14, 15, 3, 7, 8, 11, 7, 12, 14, 15, 8,
+    14, 13, 15, 11, 15, 15, 16,
+];
+
+/// number of orbits of graphlets with 2 to 4 vertexes
+pub const ORBITS: usize = 15;
+
+/// przulj orbit of a vertex by `UNDIRECTED_MOTIFS` index and its degree inside the motif
+const ORBIT_BY_DEGREE: [[usize; 4]; 8] = [
+    [0, 1, 2, 0],
+    [0, 0, 3, 0],
+    [0, 4, 5, 0],
+    [0, 6, 0, 7],
+    [0, 0, 8, 0],
+    [0, 9, 10, 11],
+    [0, 0, 12, 13],
+    [0, 0, 0, 14],
+];
+
+/// csr rows are sorted
+fn is_adjacent(neighbors: &Csr, u: usize, v: usize) -> bool {
+    neighbors.out_neighbors(u).binary_search(&v).is_ok()
+}
+
+/// esu enumeration (wernicke), `visit` is called exactly once for every connected
+/// vertex set with 2 to `k` vertexes
+fn enumerate_connected<F>(neighbors: &Csr, k: usize, visit: &mut F)
+where
+    F: FnMut(&[usize]),
+{
+    fn extend<F: FnMut(&[usize])>(
+        neighbors: &Csr,
+        k: usize,
+        root: usize,
+        sub: &mut Vec<usize>,
+        mut extension: Vec<usize>,
+        visit: &mut F,
+    ) {
+        if sub.len() > 1 {
+            visit(sub);
+        }
+        if sub.len() == k {
+            return;
+        }
+        while let Some(w) = extension.pop() {
+            let mut next = extension.clone();
+            for u in neighbors.out_neighbors(w) {
+                // exclusive neighbors of w: not in sub and not adjacent to sub
+                if *u > root
+                    && !sub.contains(u)
+                    && !next.contains(u)
+                    && sub.iter().all(|s| !is_adjacent(neighbors, *s, *u))
+                {
+                    next.push(*u);
+                }
+            }
+            sub.push(w);
+            extend(neighbors, k, root, sub, next, visit);
+            sub.pop();
+        }
+    }
+    for root in 0..neighbors.len() {
+        let extension: Vec<usize> = neighbors
+            .out_neighbors(root)
+            .iter()
+            .filter(|u| **u > root)
+            .copied()
+            .collect();
+        let mut sub = vec![root];
+        extend(neighbors, k, root, &mut sub, extension, visit);
+    }
+}
+
+/// degree of every vertex of `sub` inside the induced subgraph, and the number of edges
+fn induced_degree(neighbors: &Csr, sub: &[usize]) -> (Vec<usize>, usize) {
+    let mut degree = vec![0; sub.len()];
+    let mut edges = 0;
+    for i in 0..sub.len() {
+        for j in (i + 1)..sub.len() {
+            if is_adjacent(neighbors, sub[i], sub[j]) {
+                degree[i] += 1;
+                degree[j] += 1;
+                edges += 1;
+            }
+        }
+    }
+    (degree, edges)
+}
+
+/// index of the connected induced subgraph in `UNDIRECTED_MOTIFS`
+fn classify_undirected(degree: &[usize], edges: usize) -> usize {
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+    match (degree.len(), edges) {
+        (3, 2) => 0,
+        (3, _) => 1,
+        (4, 3) if max_degree == 3 => 3,
+        (4, 3) => 2,
+        (4, 4) if max_degree == 3 => 5,
+        (4, 4) => 4,
+        (4, 5) => 6,
+        _ => 7,
+    }
+}
+
+/// induced counts of the connected 3 and 4 vertex motifs in `UNDIRECTED_MOTIFS` order,
+/// directed edges are treated as undirected
+pub fn make_undirected_motifs(graph: &Graph) -> Vec<u64> {
+    let neighbors = graph.snapshot().undirected();
+    let mut counts = vec![0; UNDIRECTED_MOTIFS.len()];
+    enumerate_connected(&neighbors, 4, &mut |sub| {
+        if sub.len() > 2 {
+            let (degree, edges) = induced_degree(&neighbors, sub);
+            counts[classify_undirected(&degree, edges)] += 1;
+        }
+    });
+    counts
+}
+
+/// graphlet degree vector (przulj orbits 0 to 14) of every vertex
+pub fn make_graphlet_degree_vectors(graph: &Graph) -> Vec<[u64; ORBITS]> {
+    let neighbors = graph.snapshot().undirected();
+    let mut gdv = vec![[0; ORBITS]; neighbors.len()];
+    enumerate_connected(&neighbors, 4, &mut |sub| {
+        let (degree, edges) = induced_degree(&neighbors, sub);
+        let motif = if sub.len() == 2 {
+            usize::MAX
+        } else {
+            classify_undirected(&degree, edges)
+        };
+        for (i, v) in sub.iter().enumerate() {
+            let orbit = match motif {
+                usize::MAX => 0,
+                _ => ORBIT_BY_DEGREE[motif][degree[i]],
+            };
+            gdv[*v][orbit] += 1;
+        }
+    });
+    gdv
+}
+
+fn has_arc(graph: &Graph, u: usize, v: usize) -> bool {
+    graph.neighbors_map[u].contains_key(&v)
+}
+
+fn tricode(graph: &Graph, v: usize, u: usize, w: usize) -> usize {
+    [
+        (v, u, 1),
+        (u, v, 2),
+        (v, w, 4),
+        (w, v, 8),
+        (u, w, 16),
+        (w, u, 32),
+    ]
+    .iter()
+    .filter(|(a, b, _)| has_arc(graph, *a, *b))
+    .map(|(_, _, bit)| bit)
+    .sum()
+}
+
+/// directed triad census in `TRIAD_NAMES` order, the connected types are enumerated
+/// and the ones with an isolated vertex are derived from the dyads
+pub fn make_triad_census(graph: &Graph) -> Vec<u64> {
+    let neighbors = graph.snapshot().undirected();
+    let len = neighbors.len() as u64;
+    let mut census = vec![0; TRIAD_NAMES.len()];
+    enumerate_connected(&neighbors, 3, &mut |sub| {
+        if sub.len() == 3 {
+            census[TRICODES[tricode(graph, sub[0], sub[1], sub[2])] - 1] += 1;
+        }
+    });
+    for (u, list) in (0..neighbors.len()).map(|u| (u, neighbors.out_neighbors(u))) {
+        for v in list.iter().filter(|v| **v > u) {
+            // vertexes adjacent to neither u nor v
+            let mut union = list.len() + neighbors.out_neighbors(*v).len();
+            union -= neighbors
+                .out_neighbors(*v)
+                .iter()
+                .filter(|w| is_adjacent(&neighbors, u, **w))
+                .count();
+            let isolated = len - union as u64;
+            let mutual = has_arc(graph, u, *v) && has_arc(graph, *v, u);
+            census[if mutual { 2 } else { 1 }] += isolated;
+        }
+    }
+    let total = if len < 3 {
+        0
+    } else {
+        len * (len - 1) * (len - 2) / 6
+    };
+    census[0] = total - census.iter().sum::<u64>();
+    census
+}
+
+/// counts of connected 4 vertex directed motifs, keyed by their canonical 12 bit adjacency code.
+/// bit `4 * i + j` (j skipping i) is set when there is an arc from the i-th to the j-th vertex,
+/// the canonical code is the smallest one over all vertex orders
+pub fn make_directed_motifs4(graph: &Graph) -> BTreeMap<u16, u64> {
+    let neighbors = graph.snapshot().undirected();
+    let mut counts = BTreeMap::new();
+    let mut permutations = vec![];
+    for a in 0..4 {
+        for b in 0..4 {
+            for c in 0..4 {
+                if a != b && a != c && b != c {
+                    permutations.push([a, b, c, 6 - a - b - c]);
+                }
+            }
+        }
+    }
+    enumerate_connected(&neighbors, 4, &mut |sub| {
+        if sub.len() < 4 {
+            return;
+        }
+        let code = permutations
+            .iter()
+            .map(|perm| {
+                let mut code = 0u16;
+                let mut bit = 0;
+                for i in 0..4 {
+                    for j in 0..4 {
+                        if i == j {
+                            continue;
+                        }
+                        if has_arc(graph, sub[perm[i]], sub[perm[j]]) {
+                            code |= 1 << bit;
+                        }
+                        bit += 1;
+                    }
+                }
+                code
+            })
+            .min()
+            .unwrap();
+        *counts.entry(code).or_insert(0) += 1;
+    });
+    counts
+}
+
+/// counts in `UNDIRECTED_MOTIFS` order
+#[wasm_bindgen]
+pub fn undirected_motifs(graph: &Graph) -> Vec<f64> {
+    make_undirected_motifs(graph)
+        .iter()
+        .map(|v| *v as f64)
+        .collect()
+}
+
+/// counts in `TRIAD_NAMES` order
+#[wasm_bindgen]
+pub fn triad_census(graph: &Graph) -> Vec<f64> {
+    make_triad_census(graph).iter().map(|v| *v as f64).collect()
+}
+
+/// return a flat array of [canonical code, count, ...]
+#[wasm_bindgen]
+pub fn directed_motifs4(graph: &Graph) -> Vec<f64> {
+    make_directed_motifs4(graph)
+        .iter()
+        .flat_map(|(code, count)| [*code as f64, *count as f64])
+        .collect()
+}
+
+/// return a flat len * 15 array
+#[wasm_bindgen]
+pub fn graphlet_degree_vectors(graph: &Graph) -> Vec<f64> {
+    make_graphlet_degree_vectors(graph)
+        .iter()
+        .flatten()
+        .map(|v| *v as f64)
+        .collect()
+}
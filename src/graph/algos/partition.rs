@@ -0,0 +1,332 @@
+use crate::graph::{
+    algos::mincut::{make_undirected_weights, Candidate},
+    utils::random::SeededRng,
+    Graph,
+};
+use std::collections::{BinaryHeap, HashMap};
+use wasm_bindgen::prelude::*;
+
+/// allowed deviation of a part weight from its target, relative to the target
+const IMBALANCE: f64 = 0.03;
+/// coarsening stops at this many vertexes
+const COARSEST: usize = 40;
+
+/// weighted undirected graph used while coarsening
+struct Level {
+    adjacency: Vec<Vec<(usize, f64)>>,
+    weight: Vec<f64>,
+}
+
+impl Level {
+    fn len(&self) -> usize {
+        self.weight.len()
+    }
+
+    fn cut(&self, side: &[bool]) -> f64 {
+        let mut cut = 0.;
+        for (v, list) in self.adjacency.iter().enumerate() {
+            for (u, w) in list {
+                if v < *u && side[v] != side[*u] {
+                    cut += w;
+                }
+            }
+        }
+        cut
+    }
+
+    /// heavy edge matching, return the coarse level and the coarse vertex of every vertex
+    fn coarsen(&self, rng: &mut SeededRng) -> (Level, Vec<usize>) {
+        let len = self.len();
+        let mut order: Vec<usize> = (0..len).collect();
+        for i in (1..len).rev() {
+            order.swap(i, rng.next_index(i + 1));
+        }
+        let mut coarse = vec![usize::MAX; len];
+        let mut count = 0;
+        for v in order {
+            if coarse[v] != usize::MAX {
+                continue;
+            }
+            let mate = self.adjacency[v]
+                .iter()
+                .filter(|(u, _)| coarse[*u] == usize::MAX && *u != v)
+                .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+                .map(|(u, _)| *u);
+            coarse[v] = count;
+            if let Some(u) = mate {
+                coarse[u] = count;
+            }
+            count += 1;
+        }
+        let mut weight = vec![0.; count];
+        let mut merged: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+        for v in 0..len {
+            weight[coarse[v]] += self.weight[v];
+            for (u, w) in &self.adjacency[v] {
+                if coarse[v] != coarse[*u] {
+                    *merged[coarse[v]].entry(coarse[*u]).or_insert(0.) += w;
+                }
+            }
+        }
+        let adjacency = merged
+            .into_iter()
+            .map(|map| {
+                let mut list: Vec<(usize, f64)> = map.into_iter().collect();
+                list.sort_by_key(|(u, _)| *u);
+                list
+            })
+            .collect();
+        (Level { adjacency, weight }, coarse)
+    }
+
+    /// grow side `true` by bfs from `seed` until it holds `target` weight
+    fn grow(&self, seed: usize, target: f64) -> Vec<bool> {
+        let len = self.len();
+        let mut side = vec![false; len];
+        let mut total = 0.;
+        let mut queue = std::collections::VecDeque::from(vec![seed]);
+        let mut queued = vec![false; len];
+        queued[seed] = true;
+        let mut next_seed = 0;
+        while total < target {
+            let v = match queue.pop_front() {
+                Some(v) => v,
+                None => {
+                    // disconnected, continue from any vertex not taken yet
+                    while next_seed < len && queued[next_seed] {
+                        next_seed += 1;
+                    }
+                    if next_seed == len {
+                        break;
+                    }
+                    queued[next_seed] = true;
+                    next_seed
+                }
+            };
+            side[v] = true;
+            total += self.weight[v];
+            for (u, _) in &self.adjacency[v] {
+                if !queued[*u] {
+                    queued[*u] = true;
+                    queue.push_back(*u);
+                }
+            }
+        }
+        side
+    }
+
+    /// fiduccia-mattheyses passes, keep the weight of side `true` within [low, high].
+    /// a side that starts out of balance is first moved towards the range
+    fn refine(&self, side: &mut [bool], low: f64, high: f64) {
+        let len = self.len();
+        let mut true_weight: f64 = (0..len).filter(|v| side[*v]).map(|v| self.weight[v]).sum();
+        let excess = |w: f64| (low - w).max(w - high).max(0.);
+        for _ in 0..8 {
+            let mut gain: Vec<f64> = (0..len)
+                .map(|v| {
+                    self.adjacency[v]
+                        .iter()
+                        .map(|(u, w)| if side[*u] != side[v] { *w } else { -*w })
+                        .sum()
+                })
+                .collect();
+            let mut heap: BinaryHeap<Candidate> = (0..len).map(|v| Candidate(gain[v], v)).collect();
+            let mut locked = vec![false; len];
+            let mut moves = vec![];
+            let mut total = 0.;
+            // best prefix ranks by imbalance first, then by cut gain
+            let (mut best_excess, mut best, mut best_len) = (excess(true_weight), 0., 0);
+            let patience = (len / 20).max(50);
+            let mut skipped = vec![];
+            while let Some(Candidate(g, v)) = heap.pop() {
+                if locked[v] || g != gain[v] {
+                    continue;
+                }
+                let next_weight = if side[v] {
+                    true_weight - self.weight[v]
+                } else {
+                    true_weight + self.weight[v]
+                };
+                if excess(next_weight) > 0. && excess(next_weight) >= excess(true_weight) {
+                    skipped.push(v);
+                    continue;
+                }
+                locked[v] = true;
+                side[v] = !side[v];
+                true_weight = next_weight;
+                total += g;
+                moves.push(v);
+                gain[v] = -g;
+                for (u, w) in &self.adjacency[v] {
+                    if locked[*u] {
+                        continue;
+                    }
+                    gain[*u] += if side[*u] == side[v] { -2. * w } else { 2. * w };
+                    heap.push(Candidate(gain[*u], *u));
+                }
+                // vertexes skipped for balance may fit after this move
+                for u in skipped.drain(..) {
+                    heap.push(Candidate(gain[u], u));
+                }
+                let now_excess = excess(true_weight);
+                if now_excess < best_excess - 1e-9
+                    || (now_excess <= best_excess + 1e-9 && total > best + 1e-9)
+                {
+                    best_excess = now_excess;
+                    best = total;
+                    best_len = moves.len();
+                } else if moves.len() - best_len > patience {
+                    break;
+                }
+            }
+            // roll back the moves after the best prefix
+            for v in moves.drain(best_len..) {
+                side[v] = !side[v];
+                true_weight += if side[v] {
+                    self.weight[v]
+                } else {
+                    -self.weight[v]
+                };
+            }
+            if best_len == 0 {
+                break;
+            }
+        }
+    }
+
+    /// multilevel bisection, side `true` gets `ratio` of the total weight
+    fn bisect(&self, ratio: f64, rng: &mut SeededRng) -> Vec<bool> {
+        let total: f64 = self.weight.iter().sum();
+        let target = total * ratio;
+        let max_weight = self.weight.iter().cloned().fold(0., f64::max);
+        let slack = (target * IMBALANCE).max(max_weight);
+        let (low, high) = (target - slack, target + slack);
+        if self.len() > COARSEST {
+            let (coarse, map) = self.coarsen(rng);
+            if coarse.len() < self.len() * 9 / 10 {
+                let coarse_side = coarse.bisect(ratio, rng);
+                let mut side: Vec<bool> = map.iter().map(|c| coarse_side[*c]).collect();
+                self.refine(&mut side, low, high);
+                return side;
+            }
+        }
+        // coarsest level: best of a few greedy growings
+        let mut best: Option<(f64, Vec<bool>)> = None;
+        for _ in 0..4 {
+            let mut side = self.grow(rng.next_index(self.len()), target);
+            self.refine(&mut side, low, high);
+            let cut = self.cut(&side);
+            if best.as_ref().map(|(c, _)| cut < *c).unwrap_or(true) {
+                best = Some((cut, side));
+            }
+        }
+        best.unwrap().1
+    }
+
+    fn subgraph(&self, vertexes: &[usize]) -> Level {
+        let mut local = HashMap::new();
+        for (i, v) in vertexes.iter().enumerate() {
+            local.insert(*v, i);
+        }
+        let adjacency = vertexes
+            .iter()
+            .map(|v| {
+                self.adjacency[*v]
+                    .iter()
+                    .filter_map(|(u, w)| local.get(u).map(|i| (*i, *w)))
+                    .collect()
+            })
+            .collect();
+        Level {
+            adjacency,
+            weight: vertexes.iter().map(|v| self.weight[*v]).collect(),
+        }
+    }
+}
+
+fn partition_recursive(
+    level: &Level,
+    vertexes: &[usize],
+    k: usize,
+    first_part: usize,
+    parts: &mut [usize],
+    rng: &mut SeededRng,
+) {
+    if k <= 1 || vertexes.len() <= 1 {
+        vertexes.iter().for_each(|v| parts[*v] = first_part);
+        return;
+    }
+    let left_k = k / 2;
+    let sub = level.subgraph(vertexes);
+    let side = sub.bisect(left_k as f64 / k as f64, rng);
+    let left: Vec<usize> = (0..vertexes.len())
+        .filter(|i| side[*i])
+        .map(|i| vertexes[i])
+        .collect();
+    let right: Vec<usize> = (0..vertexes.len())
+        .filter(|i| !side[*i])
+        .map(|i| vertexes[i])
+        .collect();
+    partition_recursive(level, &left, left_k, first_part, parts, rng);
+    partition_recursive(level, &right, k - left_k, first_part + left_k, parts, rng);
+}
+
+/// split the undirected view of the graph into `k` parts of about the same size with a small
+/// edge cut, by recursive multilevel bisection (heavy edge matching, greedy growing, fm refinement)
+pub fn make_partition(graph: &Graph, k: usize, seed: u64) -> Vec<usize> {
+    let len = graph.vertexes.len();
+    let level = Level {
+        adjacency: make_undirected_weights(graph)
+            .into_iter()
+            .map(|map| {
+                let mut list: Vec<(usize, f64)> = map.into_iter().collect();
+                list.sort_by_key(|(u, _)| *u);
+                list
+            })
+            .collect(),
+        weight: vec![1.; len],
+    };
+    let mut parts = vec![0; len];
+    let mut rng = SeededRng::new(seed);
+    let vertexes: Vec<usize> = (0..len).collect();
+    partition_recursive(&level, &vertexes, k.max(1), 0, &mut parts, &mut rng);
+    parts
+}
+
+/// total |weight| of the edges between different parts
+pub fn make_edge_cut(graph: &Graph, parts: &[usize]) -> f32 {
+    graph
+        .edges
+        .iter()
+        .filter(|edge| parts[edge.source] != parts[edge.target])
+        .map(|edge| edge.weight.abs())
+        .sum()
+}
+
+/// part of every vertex
+#[wasm_bindgen]
+pub fn partition_graph(graph: &Graph, k: usize, seed: u32) -> Vec<u32> {
+    make_partition(graph, k, seed as u64)
+        .iter()
+        .map(|p| *p as u32)
+        .collect()
+}
+
+/// like `partition_graph` with an edge number column as the weights, see `Graph::use_edge_weight`.
+/// empty when there is no number column of this name
+#[wasm_bindgen]
+pub fn partition_graph_by_weight(graph: &mut Graph, k: usize, seed: u32, weight: &str) -> Vec<u32> {
+    graph
+        .with_edge_weight(weight, |graph| partition_graph(graph, k, seed))
+        .unwrap_or_default()
+}
+
+/// edge cut between the groups of a vertex attribute, see `Attributes::groups`.
+/// NaN when there is no column of this name
+#[wasm_bindgen]
+pub fn group_edge_cut(graph: &Graph, name: &str) -> f32 {
+    match graph.vertex_attributes.groups(name) {
+        Some(groups) => make_edge_cut(graph, &groups),
+        None => f32::NAN,
+    }
+}
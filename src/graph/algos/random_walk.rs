@@ -0,0 +1,128 @@
+use crate::graph::{utils::random::SeededRng, Graph};
+use wasm_bindgen::prelude::*;
+
+/// fills the rest of a walk that hits a vertex without out neighbors
+pub const WALK_PADDING: u32 = u32::MAX;
+
+/// `walks_per_vertex` rounds, every round starts one walk from each vertex in index order.
+/// each walk takes `walk_length` slots of the result, walks ending early are padded with `WALK_PADDING`
+fn make_walks<F>(
+    graph: &Graph,
+    walk_length: usize,
+    walks_per_vertex: usize,
+    mut step: F,
+) -> Vec<u32>
+where
+    F: FnMut(&[usize]) -> Option<usize>,
+{
+    let len = graph.vertexes.len();
+    if walk_length == 0 {
+        return vec![];
+    }
+    let mut walks = Vec::with_capacity(len * walks_per_vertex * walk_length);
+    let mut walk = Vec::with_capacity(walk_length);
+    for _ in 0..walks_per_vertex {
+        for start in 0..len {
+            walk.clear();
+            walk.push(start);
+            while walk.len() < walk_length {
+                match step(&walk) {
+                    Some(next) => walk.push(next),
+                    None => break,
+                }
+            }
+            walks.extend(walk.iter().map(|v| *v as u32));
+            walks.resize(walks.len() + walk_length - walk.len(), WALK_PADDING);
+        }
+    }
+    walks
+}
+
+pub fn make_uniform_walks(
+    graph: &Graph,
+    walk_length: usize,
+    walks_per_vertex: usize,
+    seed: u64,
+) -> Vec<u32> {
+    // csr rows are sorted by index, HashMap order differs between runs so it can't be used for sampling
+    let csr = graph.snapshot();
+    let mut rng = SeededRng::new(seed);
+    make_walks(graph, walk_length, walks_per_vertex, |walk| {
+        let list = csr.out_neighbors(walk[walk.len() - 1]);
+        if list.is_empty() {
+            None
+        } else {
+            Some(list[rng.next_index(list.len())])
+        }
+    })
+}
+
+/// second order walk from node2vec, the next vertex x of t -> v is picked with weight(v, x) scaled by
+/// 1/p when x == t, 1 when x is a neighbor of t and 1/q otherwise
+pub fn make_node2vec_walks(
+    graph: &Graph,
+    walk_length: usize,
+    walks_per_vertex: usize,
+    p: f32,
+    q: f32,
+    seed: u64,
+) -> Vec<u32> {
+    let csr = graph.snapshot();
+    let mut rng = SeededRng::new(seed);
+    let mut probs: Vec<f64> = vec![];
+    make_walks(graph, walk_length, walks_per_vertex, |walk| {
+        let cur = walk[walk.len() - 1];
+        let list = csr.out_neighbors(cur);
+        if list.is_empty() {
+            return None;
+        }
+        probs.clear();
+        if walk.len() == 1 {
+            probs.extend(csr.out_weights(cur).iter().map(|w| w.abs() as f64));
+        } else {
+            let prev = walk[walk.len() - 2];
+            let prev_list = csr.out_neighbors(prev);
+            probs.extend(csr.out_edges(cur).map(|(x, w)| {
+                let bias = if x == prev {
+                    1. / p
+                } else if prev_list.binary_search(&x).is_ok() {
+                    1.
+                } else {
+                    1. / q
+                };
+                (w.abs() * bias) as f64
+            }));
+        }
+        let total: f64 = probs.iter().sum();
+        let mut target = rng.next_f64() * total;
+        for (i, prob) in probs.iter().enumerate() {
+            if target < *prob {
+                return Some(list[i]);
+            }
+            target -= prob;
+        }
+        Some(list[list.len() - 1])
+    })
+}
+
+#[wasm_bindgen]
+pub fn uniform_random_walks(
+    graph: &Graph,
+    walk_length: usize,
+    walks_per_vertex: usize,
+    seed: u32,
+) -> Vec<u32> {
+    make_uniform_walks(graph, walk_length, walks_per_vertex, seed as u64)
+}
+
+#[wasm_bindgen]
+pub fn node2vec_walks(
+    graph: &Graph,
+    walk_length: usize,
+    walks_per_vertex: usize,
+    p: f32,
+    q: f32,
+    seed: u32,
+) -> Vec<u32> {
+    make_node2vec_walks(graph, walk_length, walks_per_vertex, p, q, seed as u64)
+}
@@ -0,0 +1,158 @@
+use crate::graph::{algos::scc, Graph};
+use wasm_bindgen::prelude::*;
+
+/// transitive closure of the scc condensation, compressed into intervals (agrawal, borgida and
+/// jagadish). components are numbered in post order of a dfs spanning forest, so everything a
+/// component reaches through the forest is one interval and only cross edges add more.
+/// trees and chains need one interval per component, dense dags still grow towards C² / 2.
+/// vertexes of a component share their intervals, a query is a binary search.
+/// it is a snapshot, build a new one after the graph changes
+#[wasm_bindgen]
+pub struct Reachability {
+    component: Vec<usize>,
+    members: Vec<Vec<usize>>,
+    /// post order number of every component
+    post: Vec<usize>,
+    /// component of every post order number
+    by_post: Vec<usize>,
+    /// sorted, disjoint and non adjacent [start, end] post order intervals
+    intervals: Vec<Vec<(usize, usize)>>,
+}
+
+impl Reachability {
+    fn contains(&self, from: usize, to: usize) -> bool {
+        let target = self.post[to];
+        let intervals = &self.intervals[from];
+        match intervals.partition_point(|(start, _)| *start <= target) {
+            0 => false,
+            i => intervals[i - 1].1 >= target,
+        }
+    }
+
+    fn reached_components(&self, from: usize) -> impl Iterator<Item = usize> + '_ {
+        self.intervals[from]
+            .iter()
+            .flat_map(move |(start, end)| self.by_post[*start..=*end].iter().copied())
+    }
+}
+
+#[wasm_bindgen]
+impl Reachability {
+    #[wasm_bindgen(constructor)]
+    pub fn new(graph: &Graph) -> Reachability {
+        let csr = graph.snapshot();
+        let (component, count) = scc::make_csr_scc(&csr);
+        let mut members = vec![vec![]; count];
+        let mut successors = vec![vec![]; count];
+        for (v, c) in component.iter().enumerate() {
+            members[*c].push(v);
+            for w in csr.out_neighbors(v) {
+                if component[*w] != *c {
+                    successors[*c].push(component[*w]);
+                }
+            }
+        }
+        for list in successors.iter_mut() {
+            list.sort_unstable();
+            list.dedup();
+        }
+        // iterative dfs from the sources, tarjan numbers them last
+        let mut post = vec![usize::MAX; count];
+        let mut low = vec![0; count];
+        let mut by_post = Vec::with_capacity(count);
+        let mut visited = vec![false; count];
+        for root in (0..count).rev() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            low[root] = by_post.len();
+            let mut stack = vec![(root, 0)];
+            while let Some((c, next)) = stack.last_mut() {
+                let c = *c;
+                match successors[c].get(*next) {
+                    Some(s) => {
+                        *next += 1;
+                        if !visited[*s] {
+                            visited[*s] = true;
+                            low[*s] = by_post.len();
+                            stack.push((*s, 0));
+                        }
+                    }
+                    None => {
+                        post[c] = by_post.len();
+                        by_post.push(c);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        // tarjan numbers sinks first, so the intervals of every successor are complete before
+        // they are merged
+        let mut intervals: Vec<Vec<(usize, usize)>> = Vec::with_capacity(count);
+        let mut pending = vec![];
+        for c in 0..count {
+            pending.clear();
+            pending.push((low[c], post[c]));
+            for s in &successors[c] {
+                pending.extend_from_slice(&intervals[*s]);
+            }
+            pending.sort_unstable();
+            let mut merged: Vec<(usize, usize)> = vec![];
+            for (start, end) in pending.iter().copied() {
+                match merged.last_mut() {
+                    Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+                    _ => merged.push((start, end)),
+                }
+            }
+            merged.shrink_to_fit();
+            intervals.push(merged);
+        }
+        Reachability {
+            component,
+            members,
+            post,
+            by_post,
+            intervals,
+        }
+    }
+
+    /// every vertex reaches itself
+    pub fn can_reach(&self, source: usize, target: usize) -> bool {
+        self.contains(self.component[source], self.component[target])
+    }
+
+    /// every vertex reachable from `source`, including itself, in ascending order
+    pub fn descendants(&self, source: usize) -> Vec<u32> {
+        let mut result: Vec<u32> = self
+            .reached_components(self.component[source])
+            .flat_map(|c| self.members[c].iter().map(|v| *v as u32))
+            .collect();
+        result.sort_unstable();
+        result
+    }
+
+    /// every vertex that reaches `target`, including itself, in ascending order
+    pub fn ancestors(&self, target: usize) -> Vec<u32> {
+        let to = self.component[target];
+        let mut result: Vec<u32> = (0..self.members.len())
+            .filter(|c| self.contains(*c, to))
+            .flat_map(|c| self.members[c].iter().map(|v| *v as u32))
+            .collect();
+        result.sort_unstable();
+        result
+    }
+
+    pub fn component_of(&self, index: usize) -> usize {
+        self.component[index]
+    }
+
+    pub fn components_len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// stored intervals over all components, a measure of how well the closure compressed
+    pub fn intervals_len(&self) -> usize {
+        self.intervals.iter().map(|list| list.len()).sum()
+    }
+}
@@ -0,0 +1,52 @@
+use crate::graph::Graph;
+use wasm_bindgen::prelude::*;
+
+/// random walk with restart, the walker follows out edges proportionally to |weight|
+/// and jumps back to `source` with probability `restart`.
+/// dangling vertexes send all their mass back to `source`, so the scores always sum to 1
+pub fn make_rwr(graph: &Graph, source: usize, restart: f32, max_iter: usize) -> Vec<f32> {
+    let len = graph.vertexes.len();
+    let csr = graph.snapshot();
+    let out_weight: Vec<f32> = (0..len)
+        .map(|v| csr.out_weights(v).iter().map(|w| w.abs()).sum())
+        .collect();
+    let mut p = vec![0.; len];
+    p[source] = 1.;
+    for _ in 0..max_iter {
+        let mut next = vec![0.; len];
+        let mut dangling = 0.;
+        for src in 0..len {
+            if p[src] == 0. {
+                continue;
+            }
+            if out_weight[src] == 0. {
+                dangling += p[src];
+                continue;
+            }
+            let mass = (1. - restart) * p[src] / out_weight[src];
+            for (dst, weight) in csr.out_edges(src) {
+                next[dst] += mass * weight.abs();
+            }
+        }
+        next[source] += restart + (1. - restart) * dangling;
+        let delta: f32 = next.iter().zip(&p).map(|(a, b)| (a - b).abs()).sum();
+        p = next;
+        if delta < 1e-6 {
+            break;
+        }
+    }
+    p
+}
+
+#[wasm_bindgen]
+pub fn random_walk_with_restart(
+    graph: &Graph,
+    id: &str,
+    restart: f32,
+    max_iter: usize,
+) -> Vec<f32> {
+    match graph.get_vertex_by_id(id) {
+        Some(index) => make_rwr(graph, index, restart, max_iter),
+        None => vec![],
+    }
+}
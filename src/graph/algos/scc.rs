@@ -0,0 +1,67 @@
+use crate::graph::{csr::Csr, Graph};
+
+/// iterative tarjan, return the component of every vertex.
+/// components are numbered in reverse topological order of the condensation: a component only
+/// has edges into components with smaller numbers
+pub fn make_scc(graph: &Graph) -> (Vec<usize>, usize) {
+    make_csr_scc(&graph.snapshot())
+}
+
+pub fn make_csr_scc(csr: &Csr) -> (Vec<usize>, usize) {
+    let len = csr.len();
+    let mut index = vec![usize::MAX; len];
+    let mut low = vec![0; len];
+    let mut on_stack = vec![false; len];
+    let mut component = vec![usize::MAX; len];
+    let mut stack = vec![];
+    let mut next_index = 0;
+    let mut count = 0;
+    for root in 0..len {
+        if index[root] != usize::MAX {
+            continue;
+        }
+        // (vertex, position of the next neighbor to visit)
+        let mut call_stack = vec![(root, 0)];
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        while let Some((v, pos)) = call_stack.pop() {
+            if pos < csr.out_neighbors(v).len() {
+                call_stack.push((v, pos + 1));
+                let w = csr.out_neighbors(v)[pos];
+                if index[w] == usize::MAX {
+                    index[w] = next_index;
+                    low[w] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    call_stack.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(index[w]);
+                }
+                continue;
+            }
+            if low[v] == index[v] {
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component[w] = count;
+                    if w == v {
+                        break;
+                    }
+                }
+                count += 1;
+            }
+            if let Some((parent, _)) = call_stack.last() {
+                low[*parent] = low[*parent].min(low[v]);
+            }
+        }
+    }
+    (component, count)
+}
+
+/// component of every vertex
+pub fn run(graph: &Graph) -> Vec<f32> {
+    make_scc(graph).0.iter().map(|c| *c as f32).collect()
+}
@@ -0,0 +1,143 @@
+use crate::graph::Graph;
+use std::collections::HashSet;
+use wasm_bindgen::prelude::*;
+
+/// vertex pair similarity used by link prediction, all of them only need `neighbors_map`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Similarity {
+    CommonNeighbors,
+    Jaccard,
+    AdamicAdar,
+    ResourceAllocation,
+    PreferentialAttachment,
+}
+
+impl Similarity {
+    pub fn from_name(name: &str) -> Option<Similarity> {
+        match name {
+            "common_neighbors" | "cn" => Some(Similarity::CommonNeighbors),
+            "jaccard" => Some(Similarity::Jaccard),
+            "adamic_adar" | "aa" => Some(Similarity::AdamicAdar),
+            "resource_allocation" | "ra" => Some(Similarity::ResourceAllocation),
+            "preferential_attachment" | "pa" => Some(Similarity::PreferentialAttachment),
+            _ => None,
+        }
+    }
+}
+
+/// degree without self loop, the neighbors_map of a directed graph only keeps out neighbors
+fn degree(graph: &Graph, index: usize) -> usize {
+    let neighbors = &graph.neighbors_map[index];
+    if neighbors.contains_key(&index) {
+        neighbors.len() - 1
+    } else {
+        neighbors.len()
+    }
+}
+
+fn common_neighbors(graph: &Graph, u: usize, v: usize) -> Vec<usize> {
+    let (small, large) = if graph.neighbors_map[u].len() <= graph.neighbors_map[v].len() {
+        (u, v)
+    } else {
+        (v, u)
+    };
+    graph.neighbors_map[small]
+        .keys()
+        .filter(|w| **w != u && **w != v && graph.neighbors_map[large].contains_key(w))
+        .copied()
+        .collect()
+}
+
+pub fn score(graph: &Graph, metric: Similarity, u: usize, v: usize) -> f32 {
+    match metric {
+        Similarity::CommonNeighbors => common_neighbors(graph, u, v).len() as f32,
+        Similarity::Jaccard => {
+            let common = common_neighbors(graph, u, v).len();
+            let union = degree(graph, u) + degree(graph, v) - common;
+            if union == 0 {
+                0.
+            } else {
+                common as f32 / union as f32
+            }
+        }
+        Similarity::AdamicAdar => common_neighbors(graph, u, v)
+            .iter()
+            .map(|w| {
+                let d = degree(graph, *w) as f32;
+                if d > 1. {
+                    1. / d.ln()
+                } else {
+                    0.
+                }
+            })
+            .sum(),
+        Similarity::ResourceAllocation => common_neighbors(graph, u, v)
+            .iter()
+            .map(|w| 1. / degree(graph, *w).max(1) as f32)
+            .sum(),
+        Similarity::PreferentialAttachment => (degree(graph, u) * degree(graph, v)) as f32,
+    }
+}
+
+/// the k vertexes most similar to `index`, sorted by score desc then index asc.
+/// vertexes with zero score are never suggested
+pub fn top_k(
+    graph: &Graph,
+    metric: Similarity,
+    index: usize,
+    k: usize,
+    exclude_neighbors: bool,
+) -> Vec<(usize, f32)> {
+    let candidates: Vec<usize> = match metric {
+        Similarity::PreferentialAttachment => (0..graph.vertexes.len()).collect(),
+        // every other metric is zero unless the vertex shares an out neighbor with `index`
+        _ => {
+            let mut two_hop = HashSet::new();
+            for w in graph.neighbors_map[index].keys() {
+                two_hop.extend(graph.in_neighbor_weights(*w).keys());
+            }
+            two_hop.into_iter().collect()
+        }
+    };
+    let mut result: Vec<(usize, f32)> = candidates
+        .into_iter()
+        .filter(|v| *v != index)
+        .filter(|v| !exclude_neighbors || !graph.neighbors_map[index].contains_key(v))
+        .map(|v| (v, score(graph, metric, index, v)))
+        .filter(|(_, s)| *s > 0.)
+        .collect();
+    result.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    result.truncate(k);
+    result
+}
+
+/// return NaN when the metric or one of the vertexes is unknown
+#[wasm_bindgen]
+pub fn vertex_similarity(graph: &Graph, metric: &str, source: &str, target: &str) -> f32 {
+    match (
+        Similarity::from_name(metric),
+        graph.get_vertex_by_id(source),
+        graph.get_vertex_by_id(target),
+    ) {
+        (Some(metric), Some(u), Some(v)) => score(graph, metric, u, v),
+        _ => f32::NAN,
+    }
+}
+
+/// return a flat array of [index, score, index, score, ...]
+#[wasm_bindgen]
+pub fn most_similar_vertexes(
+    graph: &Graph,
+    metric: &str,
+    id: &str,
+    k: usize,
+    exclude_neighbors: bool,
+) -> Vec<f32> {
+    match (Similarity::from_name(metric), graph.get_vertex_by_id(id)) {
+        (Some(metric), Some(index)) => top_k(graph, metric, index, k, exclude_neighbors)
+            .iter()
+            .flat_map(|(v, s)| [*v as f32, *s])
+            .collect(),
+        _ => vec![],
+    }
+}
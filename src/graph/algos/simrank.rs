@@ -0,0 +1,116 @@
+use crate::graph::{csr::Csr, Graph};
+use wasm_bindgen::prelude::*;
+
+/// classic iterative simrank, stop after `max_iter` rounds or when no score changes more than 1e-4
+pub fn make_simrank(graph: &Graph, decay: f32, max_iter: usize) -> Vec<Vec<f32>> {
+    let len = graph.vertexes.len();
+    let csr = graph.snapshot();
+    let mut sim = vec![vec![0.; len]; len];
+    for (i, row) in sim.iter_mut().enumerate() {
+        row[i] = 1.;
+    }
+    for _ in 0..max_iter {
+        let mut next = vec![vec![0.; len]; len];
+        let mut max_delta: f32 = 0.;
+        for a in 0..len {
+            next[a][a] = 1.;
+            for b in (a + 1)..len {
+                let (in_a, in_b) = (csr.in_neighbors(a), csr.in_neighbors(b));
+                if in_a.is_empty() || in_b.is_empty() {
+                    continue;
+                }
+                let mut sum = 0.;
+                for i in in_a {
+                    for j in in_b {
+                        sum += sim[*i][*j];
+                    }
+                }
+                let val = decay * sum / (in_a.len() * in_b.len()) as f32;
+                next[a][b] = val;
+                next[b][a] = val;
+                max_delta = max_delta.max((val - sim[a][b]).abs());
+            }
+        }
+        sim = next;
+        if max_delta < 1e-4 {
+            break;
+        }
+    }
+    sim
+}
+
+/// one step of a reversed random walk: every vertex spreads its mass to its in neighbors
+fn walk_back(csr: &Csr, x: &[f32]) -> Vec<f32> {
+    let mut next = vec![0.; x.len()];
+    for (a, mass) in x.iter().enumerate() {
+        let in_a = csr.in_neighbors(a);
+        if *mass == 0. || in_a.is_empty() {
+            continue;
+        }
+        let share = mass / in_a.len() as f32;
+        for w in in_a {
+            next[*w] += share;
+        }
+    }
+    next
+}
+
+/// transpose of `walk_back`: every vertex gathers the averaged mass of its in neighbors
+fn gather_back(csr: &Csr, y: &[f32]) -> Vec<f32> {
+    (0..csr.len())
+        .map(|a| {
+            let in_a = csr.in_neighbors(a);
+            if in_a.is_empty() {
+                0.
+            } else {
+                in_a.iter().map(|w| y[*w]).sum::<f32>() / in_a.len() as f32
+            }
+        })
+        .collect()
+}
+
+/// single source approximation with the linearized form s(u, v) = (1 - c) * sum(c^t * <P^t u, P^t v>),
+/// it costs O(steps^2 * edges) instead of the O(n^2) memory of `make_simrank`
+pub fn make_simrank_single_source(
+    graph: &Graph,
+    source: usize,
+    decay: f32,
+    steps: usize,
+) -> Vec<f32> {
+    let len = graph.vertexes.len();
+    let csr = graph.snapshot();
+    let mut scores = vec![0.; len];
+    let mut x = vec![0.; len];
+    x[source] = 1.;
+    let mut weight = 1. - decay;
+    for t in 0..=steps {
+        let mut y = x.clone();
+        for _ in 0..t {
+            y = gather_back(&csr, &y);
+        }
+        for v in 0..len {
+            scores[v] += weight * y[v];
+        }
+        x = walk_back(&csr, &x);
+        weight *= decay;
+    }
+    scores[source] = 1.;
+    scores
+}
+
+/// return a flat len * len matrix
+#[wasm_bindgen]
+pub fn simrank(graph: &Graph, decay: f32, max_iter: usize) -> Vec<f32> {
+    make_simrank(graph, decay, max_iter)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[wasm_bindgen]
+pub fn simrank_from(graph: &Graph, id: &str, decay: f32, steps: usize) -> Vec<f32> {
+    match graph.get_vertex_by_id(id) {
+        Some(index) => make_simrank_single_source(graph, index, decay, steps),
+        None => vec![],
+    }
+}
@@ -0,0 +1,266 @@
+use crate::graph::{utils::eigen::lanczos_largest, Graph};
+use wasm_bindgen::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LaplacianKind {
+    /// L = D - A
+    Combinatorial,
+    /// L = I - D^-1/2 A D^-1/2
+    Normalized,
+    /// L = I - D^-1 A
+    RandomWalk,
+}
+
+impl LaplacianKind {
+    pub fn from_name(name: &str) -> Option<LaplacianKind> {
+        match name {
+            "combinatorial" | "" => Some(LaplacianKind::Combinatorial),
+            "normalized" => Some(LaplacianKind::Normalized),
+            "random_walk" => Some(LaplacianKind::RandomWalk),
+            _ => None,
+        }
+    }
+}
+
+/// sparse laplacian of the symmetrized graph, self loops are ignored and weights are taken as |weight|.
+/// a directed edge pair u->v, v->u keeps the larger weight
+pub struct Laplacian {
+    pub kind: LaplacianKind,
+    pub degree: Vec<f64>,
+    adjacency: Vec<Vec<(usize, f64)>>,
+}
+
+impl Laplacian {
+    pub fn new(graph: &Graph, kind: LaplacianKind) -> Laplacian {
+        let len = graph.vertexes.len();
+        let mut sym = vec![std::collections::HashMap::new(); len];
+        let csr = graph.snapshot();
+        for i in 0..len {
+            for (j, w) in csr.out_edges(i) {
+                if i == j {
+                    continue;
+                }
+                let w = w.abs() as f64;
+                for (a, b) in [(i, j), (j, i)] {
+                    let entry = sym[a].entry(b).or_insert(0.);
+                    if w > *entry {
+                        *entry = w;
+                    }
+                }
+            }
+        }
+        let adjacency: Vec<Vec<(usize, f64)>> = sym
+            .into_iter()
+            .map(|m| {
+                let mut list: Vec<(usize, f64)> = m.into_iter().collect();
+                list.sort_by_key(|(j, _)| *j);
+                list
+            })
+            .collect();
+        let degree = adjacency
+            .iter()
+            .map(|list| list.iter().map(|(_, w)| w).sum())
+            .collect();
+        Laplacian {
+            kind,
+            degree,
+            adjacency,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.degree.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.degree.is_empty()
+    }
+
+    /// entry (i, j) of the matrix
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        let a = self.adjacency[i]
+            .binary_search_by_key(&j, |(k, _)| *k)
+            .map(|pos| self.adjacency[i][pos].1)
+            .unwrap_or(0.);
+        let diag = if i == j { 1. } else { 0. };
+        match self.kind {
+            LaplacianKind::Combinatorial => diag * self.degree[i] - a,
+            LaplacianKind::Normalized => {
+                let d = (self.degree[i] * self.degree[j]).sqrt();
+                diag - if d > 0. { a / d } else { 0. }
+            }
+            LaplacianKind::RandomWalk => {
+                diag - if self.degree[i] > 0. {
+                    a / self.degree[i]
+                } else {
+                    0.
+                }
+            }
+        }
+    }
+
+    pub fn to_dense(&self) -> Vec<Vec<f64>> {
+        let len = self.len();
+        (0..len)
+            .map(|i| (0..len).map(|j| self.get(i, j)).collect())
+            .collect()
+    }
+
+    /// the symmetric matrix the eigen solver works on, random walk shares the normalized one
+    fn apply_symmetric(&self, x: &[f64], y: &mut [f64]) {
+        for (i, list) in self.adjacency.iter().enumerate() {
+            match self.kind {
+                LaplacianKind::Combinatorial => {
+                    y[i] = self.degree[i] * x[i] - list.iter().map(|(j, w)| w * x[*j]).sum::<f64>();
+                }
+                _ => {
+                    let di = self.degree[i];
+                    let mut sum = 0.;
+                    if di > 0. {
+                        for (j, w) in list {
+                            sum += w * x[*j] / (di * self.degree[*j]).sqrt();
+                        }
+                    }
+                    y[i] = x[i] - sum;
+                }
+            }
+        }
+    }
+
+    fn spectral_radius_bound(&self) -> f64 {
+        match self.kind {
+            LaplacianKind::Combinatorial => 2. * self.degree.iter().cloned().fold(0., f64::max),
+            _ => 2.,
+        }
+    }
+
+    /// the `k` smallest eigenvalues in ascending order with their unit eigenvectors
+    pub fn smallest_eigenvectors(&self, k: usize) -> (Vec<f64>, Vec<Vec<f64>>) {
+        let len = self.len();
+        let sigma = self.spectral_radius_bound();
+        let steps = (k * 10).max(100);
+        let mut tmp = vec![0.; len];
+        // the smallest eigenpairs of L are the largest ones of sigma * I - L
+        let (values, mut vectors) = lanczos_largest(len, k, steps, 50, 1e-6, 0x5eed, |x, y| {
+            self.apply_symmetric(x, &mut tmp);
+            for i in 0..x.len() {
+                y[i] = sigma * x[i] - tmp[i];
+            }
+        });
+        if self.kind == LaplacianKind::RandomWalk {
+            // L_rw * (D^-1/2 v) = lambda * (D^-1/2 v) when L_sym * v = lambda * v
+            for v in vectors.iter_mut() {
+                for (i, x) in v.iter_mut().enumerate() {
+                    if self.degree[i] > 0. {
+                        *x /= self.degree[i].sqrt();
+                    }
+                }
+                let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+                if norm > 0. {
+                    v.iter_mut().for_each(|x| *x /= norm);
+                }
+            }
+        }
+        for v in vectors.iter_mut() {
+            // fix the sign so repeated runs give the same vector
+            if let Some(first) = v.iter().find(|x| x.abs() > 1e-9) {
+                if *first < 0. {
+                    v.iter_mut().for_each(|x| *x = -*x);
+                }
+            }
+        }
+        (values.into_iter().map(|v| sigma - v).collect(), vectors)
+    }
+}
+
+/// second smallest eigenpair of the combinatorial laplacian
+pub fn make_fiedler(graph: &Graph) -> (f64, Vec<f64>) {
+    let laplacian = Laplacian::new(graph, LaplacianKind::Combinatorial);
+    if laplacian.len() < 2 {
+        return (0., vec![0.; laplacian.len()]);
+    }
+    let (mut values, mut vectors) = laplacian.smallest_eigenvectors(2);
+    (values.pop().unwrap().max(0.), vectors.pop().unwrap())
+}
+
+/// vertex coordinates from the eigenvectors 2..=dims+1, the trivial first one is skipped.
+/// return a flat len * dims array
+pub fn make_spectral_embedding(graph: &Graph, kind: LaplacianKind, dims: usize) -> Vec<f32> {
+    let laplacian = Laplacian::new(graph, kind);
+    let len = laplacian.len();
+    let (_, vectors) = laplacian.smallest_eigenvectors(dims + 1);
+    let mut embedding = vec![0.; len * dims];
+    for (d, v) in vectors.iter().skip(1).enumerate() {
+        for i in 0..len {
+            embedding[i * dims + d] = v[i] as f32;
+        }
+    }
+    embedding
+}
+
+/// return [eigenvalues..., eigenvector 0..., eigenvector 1..., ...], empty for unknown kinds
+#[wasm_bindgen]
+pub fn laplacian_eigenvectors(graph: &Graph, kind: &str, k: usize) -> Vec<f32> {
+    let kind = match LaplacianKind::from_name(kind) {
+        Some(kind) => kind,
+        None => return vec![],
+    };
+    let (values, vectors) = Laplacian::new(graph, kind).smallest_eigenvectors(k);
+    values
+        .iter()
+        .chain(vectors.iter().flatten())
+        .map(|v| *v as f32)
+        .collect()
+}
+
+/// dense row major laplacian, only meant for small graphs
+#[wasm_bindgen]
+pub fn laplacian(graph: &Graph, kind: &str) -> Vec<f32> {
+    match LaplacianKind::from_name(kind) {
+        Some(kind) => Laplacian::new(graph, kind)
+            .to_dense()
+            .into_iter()
+            .flatten()
+            .map(|v| v as f32)
+            .collect(),
+        None => vec![],
+    }
+}
+
+#[wasm_bindgen]
+pub fn fiedler_vector(graph: &Graph) -> Vec<f32> {
+    make_fiedler(graph).1.iter().map(|v| *v as f32).collect()
+}
+
+#[wasm_bindgen]
+pub fn algebraic_connectivity(graph: &Graph) -> f32 {
+    make_fiedler(graph).0 as f32
+}
+
+#[wasm_bindgen]
+pub fn spectral_embedding(graph: &Graph, kind: &str, dims: usize) -> Vec<f32> {
+    match LaplacianKind::from_name(kind) {
+        Some(kind) => make_spectral_embedding(graph, kind, dims),
+        None => vec![],
+    }
+}
+
+/// use the spectral embedding as initial positions, scaled so the farthest vertex is at `scale`
+#[wasm_bindgen]
+pub fn spectral_layout(graph: &mut Graph, dims: usize, scale: f32) {
+    let dims = dims.clamp(1, 3);
+    let embedding = make_spectral_embedding(graph, LaplacianKind::RandomWalk, dims);
+    let max = embedding.iter().fold(0., |m: f32, v| m.max(v.abs()));
+    let factor = if max > 0. { scale / max } else { 0. };
+    for i in 0..graph.vertexes.len() {
+        let mut position = vec![0.; 3];
+        for d in 0..dims {
+            position[d] = embedding[i * dims + d] * factor;
+        }
+        let vertex = &mut graph.vertexes[i];
+        vertex.x = position[0] as f64;
+        vertex.y = position[1] as f64;
+        vertex.z = position[2] as f64;
+        graph.set_vertex_position(i, position);
+    }
+}
@@ -1 +1,165 @@
+use crate::graph::{csr::Csr, Graph};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 
+pub struct ShortestPaths {
+    pub dist: Vec<f32>,
+    /// previous vertex on the shortest path, usize::MAX for sources and unreachable vertexes
+    pub prev: Vec<usize>,
+    /// the source every vertex was reached from, usize::MAX when unreachable
+    pub origin: Vec<usize>,
+}
+
+impl ShortestPaths {
+    /// vertexes from the origin of `target` to `target`, empty when unreachable
+    pub fn path_to(&self, target: usize) -> Vec<usize> {
+        if self.origin[target] == usize::MAX {
+            return vec![];
+        }
+        let mut path = vec![target];
+        let mut now = target;
+        while self.prev[now] != usize::MAX {
+            now = self.prev[now];
+            path.push(now);
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// dijkstra heap entry, the heap pops the smallest distance first
+pub struct State {
+    pub dist: f32,
+    pub index: usize,
+}
+
+impl PartialEq for State {
+    fn eq(&self, other: &Self) -> bool {
+        self.dist == other.dist && self.index == other.index
+    }
+}
+
+impl Eq for State {}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for State {
+    // min heap by distance
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .dist
+            .total_cmp(&self.dist)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+/// multi source dijkstra, weights must not be negative
+pub fn make_dijkstra(csr: &Csr, sources: &[usize]) -> ShortestPaths {
+    let len = csr.len();
+    let mut dist = vec![f32::INFINITY; len];
+    let mut prev = vec![usize::MAX; len];
+    let mut origin = vec![usize::MAX; len];
+    let mut heap = BinaryHeap::new();
+    for source in sources {
+        dist[*source] = 0.;
+        origin[*source] = *source;
+        heap.push(State {
+            dist: 0.,
+            index: *source,
+        });
+    }
+    while let Some(State { dist: d, index }) = heap.pop() {
+        if d > dist[index] {
+            continue;
+        }
+        for (next, weight) in csr.out_edges(index) {
+            let next_dist = d + weight;
+            if next_dist < dist[next] {
+                dist[next] = next_dist;
+                prev[next] = index;
+                origin[next] = origin[index];
+                heap.push(State {
+                    dist: next_dist,
+                    index: next,
+                });
+            }
+        }
+    }
+    ShortestPaths { dist, prev, origin }
+}
+
+/// bellman ford for graphs with negative weights, vertexes on or behind a negative cycle get -inf
+pub fn make_bellman_ford(csr: &Csr, source: usize) -> ShortestPaths {
+    let len = csr.len();
+    let mut dist = vec![f32::INFINITY; len];
+    let mut prev = vec![usize::MAX; len];
+    dist[source] = 0.;
+    for _ in 1..len.max(2) {
+        let mut changed = false;
+        for u in 0..len {
+            if dist[u] == f32::INFINITY {
+                continue;
+            }
+            for (v, weight) in csr.out_edges(u) {
+                if dist[u] + weight < dist[v] {
+                    dist[v] = dist[u] + weight;
+                    prev[v] = u;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    // anything that can still be relaxed is reachable from a negative cycle
+    let mut stack: Vec<usize> = vec![];
+    for u in 0..len {
+        if dist[u] == f32::INFINITY {
+            continue;
+        }
+        for (v, weight) in csr.out_edges(u) {
+            if dist[u] + weight < dist[v] {
+                stack.push(v);
+            }
+        }
+    }
+    while let Some(u) = stack.pop() {
+        if dist[u] == f32::NEG_INFINITY {
+            continue;
+        }
+        dist[u] = f32::NEG_INFINITY;
+        stack.extend(csr.out_neighbors(u));
+    }
+    let origin = dist
+        .iter()
+        .map(|d| if d.is_finite() { source } else { usize::MAX })
+        .collect();
+    ShortestPaths { dist, prev, origin }
+}
+
+pub fn has_negative_weight(csr: &Csr) -> bool {
+    (0..csr.len()).any(|v| csr.out_weights(v).iter().any(|w| *w < 0.))
+}
+
+/// dijkstra when every weight is positive, otherwise bellman ford
+pub fn make_sssp(graph: &Graph, source: usize) -> ShortestPaths {
+    let csr = graph.snapshot();
+    if has_negative_weight(&csr) {
+        make_bellman_ford(&csr, source)
+    } else {
+        make_dijkstra(&csr, &[source])
+    }
+}
+
+/// distance from the `root` vertex id to every vertex
+pub fn run(graph: &Graph, root: &str) -> Vec<f32> {
+    match graph.get_vertex_by_id(root) {
+        Some(index) => make_sssp(graph, index).dist,
+        None => vec![],
+    }
+}
@@ -0,0 +1,274 @@
+use crate::graph::{attributes::Column, Edge, Graph};
+use wasm_bindgen::prelude::*;
+
+/// whole graph summary, degrees are counted from the edges so a self loop adds 2 to an undirected degree
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct GraphStats {
+    pub vertexes: usize,
+    pub edges: usize,
+    pub density: f32,
+    pub mean_degree: f32,
+    pub max_degree: usize,
+    pub assortativity: f32,
+    pub power_law_alpha: f32,
+    pub power_law_xmin: usize,
+}
+
+/// out and in degree of every vertex, for undirected graphs both are the full degree
+pub fn make_degrees(graph: &Graph) -> (Vec<usize>, Vec<usize>) {
+    let len = graph.vertexes.len();
+    let mut out_degree = vec![0; len];
+    let mut in_degree = vec![0; len];
+    for edge in &graph.edges {
+        out_degree[edge.source] += 1;
+        in_degree[edge.target] += 1;
+    }
+    if graph.is_directed {
+        (out_degree, in_degree)
+    } else {
+        let degree: Vec<usize> = out_degree
+            .iter()
+            .zip(&in_degree)
+            .map(|(a, b)| a + b)
+            .collect();
+        (degree.clone(), degree)
+    }
+}
+
+/// in + out degree for directed graphs
+pub fn make_total_degrees(graph: &Graph) -> Vec<usize> {
+    let (out_degree, in_degree) = make_degrees(graph);
+    if graph.is_directed {
+        out_degree
+            .iter()
+            .zip(&in_degree)
+            .map(|(a, b)| a + b)
+            .collect()
+    } else {
+        out_degree
+    }
+}
+
+/// histogram[k] is the number of vertexes with degree k
+pub fn make_histogram(degree: &[usize]) -> Vec<usize> {
+    let max = degree.iter().copied().max().unwrap_or(0);
+    let mut histogram = vec![0; if degree.is_empty() { 0 } else { max + 1 }];
+    for d in degree {
+        histogram[*d] += 1;
+    }
+    histogram
+}
+
+/// pearson correlation of the degrees at both ends of every edge (newman's r).
+/// directed graphs pair the out degree of the source with the in degree of the target.
+/// return NaN when every edge joins vertexes of the same degree
+pub fn make_degree_assortativity(graph: &Graph) -> f32 {
+    let (out_degree, in_degree) = make_degrees(graph);
+    let mut pairs: Vec<(f64, f64)> = vec![];
+    for edge in &graph.edges {
+        pairs.push((
+            out_degree[edge.source] as f64,
+            in_degree[edge.target] as f64,
+        ));
+        if !graph.is_directed {
+            pairs.push((
+                out_degree[edge.target] as f64,
+                in_degree[edge.source] as f64,
+            ));
+        }
+    }
+    pearson(&pairs) as f32
+}
+
+/// assortativity by a vertex attribute: pearson's r of both ends of every edge for a number
+/// column, newman's categorical r for any other column. edges with a missing number are skipped.
+/// return NaN when there is no such attribute
+pub fn make_attribute_assortativity(graph: &Graph, name: &str) -> f32 {
+    let ends = |edge: &Edge| {
+        let mut ends = vec![(edge.source, edge.target)];
+        if !graph.is_directed {
+            ends.push((edge.target, edge.source));
+        }
+        ends
+    };
+    if let Some(Column::Number(values)) = graph.vertex_attributes.column(name) {
+        let pairs: Vec<(f64, f64)> = graph
+            .edges
+            .iter()
+            .flat_map(ends)
+            .filter_map(|(a, b)| Some((values[a]?, values[b]?)))
+            .collect();
+        return pearson(&pairs) as f32;
+    }
+    let groups = match graph.vertex_attributes.groups(name) {
+        Some(groups) => groups,
+        None => return f32::NAN,
+    };
+    let count = groups.iter().max().map(|g| g + 1).unwrap_or(0);
+    // e[i][j] is the fraction of edge ends going from group i to group j
+    let mut mixing = vec![vec![0.; count]; count];
+    let pairs: Vec<(usize, usize)> = graph.edges.iter().flat_map(ends).collect();
+    for (a, b) in &pairs {
+        mixing[groups[*a]][groups[*b]] += 1. / pairs.len() as f64;
+    }
+    let trace: f64 = (0..count).map(|i| mixing[i][i]).sum();
+    let expected: f64 = (0..count)
+        .map(|i| {
+            let row: f64 = mixing[i].iter().sum();
+            let col: f64 = mixing.iter().map(|r| r[i]).sum();
+            row * col
+        })
+        .sum();
+    ((trace - expected) / (1. - expected)) as f32
+}
+
+pub fn pearson(pairs: &[(f64, f64)]) -> f64 {
+    let n = pairs.len() as f64;
+    let (mean_x, mean_y) = pairs
+        .iter()
+        .fold((0., 0.), |(x, y), (a, b)| (x + a / n, y + b / n));
+    let (mut cov, mut var_x, mut var_y) = (0., 0., 0.);
+    for (x, y) in pairs {
+        cov += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+    cov / (var_x * var_y).sqrt()
+}
+
+/// hurwitz zeta `sum (q + k)^-s` for s > 1, q >= 1: ten terms then euler-maclaurin for the rest
+fn hurwitz_zeta(s: f64, q: f64) -> f64 {
+    let n = 10.;
+    let head: f64 = (0..10).map(|k| (q + k as f64).powf(-s)).sum();
+    let a = q + n;
+    head + a.powf(1. - s) / (s - 1.) + a.powf(-s) / 2. + s * a.powf(-s - 1.) / 12.
+        - s * (s + 1.) * (s + 2.) * a.powf(-s - 3.) / 720.
+        + s * (s + 1.) * (s + 2.) * (s + 3.) * (s + 4.) * a.powf(-s - 5.) / 30240.
+}
+
+/// discrete power law fit (clauset, shalizi and newman): the exact discrete alpha MLE for every
+/// candidate x_min, keeping the x_min whose fit has the smallest kolmogorov-smirnov distance.
+/// return (alpha, x_min, ks), alpha is NaN when there are not enough positive samples
+pub fn make_power_law_fit(samples: &[usize]) -> (f64, usize, f64) {
+    let mut sorted: Vec<usize> = samples.iter().copied().filter(|d| *d > 0).collect();
+    sorted.sort_unstable();
+    let mut candidates = sorted.clone();
+    candidates.dedup();
+    let mut best = (f64::NAN, 0, f64::INFINITY);
+    for x_min in candidates {
+        let tail = &sorted[sorted.partition_point(|d| *d < x_min)..];
+        if tail.len() < 2 {
+            break;
+        }
+        if *tail.last().unwrap() == x_min {
+            // no spread left, the likelihood grows without bound in alpha
+            continue;
+        }
+        let n = tail.len() as f64;
+        let q = x_min as f64;
+        let log_sum: f64 = tail.iter().map(|d| (*d as f64).ln()).sum();
+        // the negative log likelihood is convex in alpha, golden section search
+        let cost = |alpha: f64| n * hurwitz_zeta(alpha, q).ln() + alpha * log_sum;
+        let (mut low, mut high) = (1.0001, 10.);
+        let ratio = (5f64.sqrt() - 1.) / 2.;
+        for _ in 0..80 {
+            let a = high - ratio * (high - low);
+            let b = low + ratio * (high - low);
+            if cost(a) < cost(b) {
+                high = b;
+            } else {
+                low = a;
+            }
+        }
+        let alpha = (low + high) / 2.;
+        let norm = hurwitz_zeta(alpha, q);
+        // largest gap between the empirical and fitted ccdf, compared once per distinct degree
+        // so ties don't widen it
+        let mut ks: f64 = 0.;
+        for (i, d) in tail.iter().enumerate() {
+            if i > 0 && tail[i - 1] == *d {
+                continue;
+            }
+            let empirical = 1. - i as f64 / n;
+            let fitted = hurwitz_zeta(alpha, *d as f64) / norm;
+            ks = ks.max((empirical - fitted).abs());
+        }
+        if ks < best.2 {
+            best = (alpha, x_min, ks);
+        }
+    }
+    best
+}
+
+pub fn make_stats(graph: &Graph) -> GraphStats {
+    let vertexes = graph.vertexes.len();
+    let edges = graph.edges.len();
+    let degree = make_total_degrees(graph);
+    let pairs = if graph.is_directed {
+        vertexes * vertexes.saturating_sub(1)
+    } else {
+        vertexes * vertexes.saturating_sub(1) / 2
+    };
+    let (alpha, x_min, _) = make_power_law_fit(&degree);
+    GraphStats {
+        vertexes,
+        edges,
+        density: if pairs == 0 {
+            0.
+        } else {
+            edges as f32 / pairs as f32
+        },
+        mean_degree: if vertexes == 0 {
+            0.
+        } else {
+            degree.iter().sum::<usize>() as f32 / vertexes as f32
+        },
+        max_degree: degree.iter().copied().max().unwrap_or(0),
+        assortativity: make_degree_assortativity(graph),
+        power_law_alpha: alpha as f32,
+        power_law_xmin: x_min,
+    }
+}
+
+#[wasm_bindgen]
+pub fn graph_stats(graph: &Graph) -> GraphStats {
+    make_stats(graph)
+}
+
+/// total degree histogram, in + out for directed graphs
+#[wasm_bindgen]
+pub fn degree_histogram(graph: &Graph) -> Vec<u32> {
+    make_histogram(&make_total_degrees(graph))
+        .iter()
+        .map(|v| *v as u32)
+        .collect()
+}
+
+#[wasm_bindgen]
+pub fn in_degree_histogram(graph: &Graph) -> Vec<u32> {
+    make_histogram(&make_degrees(graph).1)
+        .iter()
+        .map(|v| *v as u32)
+        .collect()
+}
+
+#[wasm_bindgen]
+pub fn out_degree_histogram(graph: &Graph) -> Vec<u32> {
+    make_histogram(&make_degrees(graph).0)
+        .iter()
+        .map(|v| *v as u32)
+        .collect()
+}
+
+#[wasm_bindgen]
+pub fn attribute_assortativity(graph: &Graph, name: &str) -> f32 {
+    make_attribute_assortativity(graph, name)
+}
+
+/// return [alpha, x_min, ks distance] of the total degree
+#[wasm_bindgen]
+pub fn power_law_fit(graph: &Graph) -> Vec<f32> {
+    let (alpha, x_min, ks) = make_power_law_fit(&make_total_degrees(graph));
+    vec![alpha as f32, x_min as f32, ks as f32]
+}
@@ -0,0 +1,145 @@
+use crate::graph::{algos::sssp, csr::Csr, utils::disjoint_set::DisjointSet, Graph};
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub struct SteinerTree {
+    edges: Vec<usize>,
+    vertexes: Vec<usize>,
+}
+
+#[wasm_bindgen]
+impl SteinerTree {
+    /// indices into the graph edges
+    pub fn edges(&self) -> Vec<u32> {
+        self.edges.iter().map(|v| *v as u32).collect()
+    }
+
+    /// non terminal vertexes the tree passes through
+    pub fn vertexes(&self) -> Vec<u32> {
+        self.vertexes.iter().map(|v| *v as u32).collect()
+    }
+}
+
+/// (smaller vertex, larger vertex) to edge index
+type EdgeLookup = HashMap<(usize, usize), usize>;
+
+/// undirected lengths |weight| and the shortest edge index behind every vertex pair
+fn make_lengths(graph: &Graph) -> (Vec<HashMap<usize, f32>>, EdgeLookup) {
+    let mut lengths = vec![HashMap::new(); graph.vertexes.len()];
+    let mut edge_of: EdgeLookup = HashMap::new();
+    for (i, edge) in graph.edges.iter().enumerate() {
+        let (u, v) = (edge.source, edge.target);
+        if u == v {
+            continue;
+        }
+        let w = edge.weight.abs();
+        let key = (u.min(v), u.max(v));
+        let shorter = match edge_of.get(&key) {
+            Some(j) => w < graph.edges[*j].weight.abs(),
+            None => true,
+        };
+        if shorter {
+            edge_of.insert(key, i);
+            lengths[u].insert(v, w);
+            lengths[v].insert(u, w);
+        }
+    }
+    (lengths, edge_of)
+}
+
+/// mehlhorn's 2-approximation: voronoi regions of the terminals from one multi source dijkstra,
+/// mst of the terminal distance graph, expand to graph paths, then mst again and prune non terminal leaves.
+/// the graph is treated as undirected with |weight| lengths, terminals in different components give a forest
+pub fn make_steiner_tree(graph: &Graph, terminals: &[usize]) -> SteinerTree {
+    let len = graph.vertexes.len();
+    let (lengths, edge_of) = make_lengths(graph);
+    let paths = sssp::make_dijkstra(&Csr::from_adjacency(&lengths), terminals);
+    // cheapest bridge between every pair of neighboring voronoi regions
+    let mut bridges: HashMap<(usize, usize), (f32, usize, usize)> = HashMap::new();
+    for (u, map) in lengths.iter().enumerate() {
+        for (v, w) in map {
+            let (a, b) = (paths.origin[u], paths.origin[*v]);
+            if u > *v || a == b || a == usize::MAX || b == usize::MAX {
+                continue;
+            }
+            let cost = paths.dist[u] + w + paths.dist[*v];
+            let entry = bridges.entry((a.min(b), a.max(b))).or_insert((cost, u, *v));
+            if cost < entry.0 {
+                *entry = (cost, u, *v);
+            }
+        }
+    }
+    let mut bridges: Vec<(f32, usize, usize, usize, usize)> = bridges
+        .into_iter()
+        .map(|((a, b), (cost, u, v))| (cost, a, b, u, v))
+        .collect();
+    bridges.sort_by(|x, y| x.0.total_cmp(&y.0).then((x.1, x.2).cmp(&(y.1, y.2))));
+    let mut set = DisjointSet::new(len);
+    let mut candidate = HashSet::new();
+    for (_, a, b, u, v) in bridges {
+        if !set.union(a, b) {
+            continue;
+        }
+        candidate.insert(edge_of[&(u.min(v), u.max(v))]);
+        for end in [u, v] {
+            let path = paths.path_to(end);
+            for pair in path.windows(2) {
+                candidate.insert(edge_of[&(pair[0].min(pair[1]), pair[0].max(pair[1]))]);
+            }
+        }
+    }
+    // mst of the expanded subgraph
+    let mut candidate: Vec<usize> = candidate.into_iter().collect();
+    candidate.sort_by(|a, b| {
+        graph.edges[*a]
+            .weight
+            .abs()
+            .total_cmp(&graph.edges[*b].weight.abs())
+            .then(a.cmp(b))
+    });
+    let mut set = DisjointSet::new(len);
+    let mut tree: Vec<usize> = candidate
+        .into_iter()
+        .filter(|i| set.union(graph.edges[*i].source, graph.edges[*i].target))
+        .collect();
+    // prune leaves that are not terminals
+    let is_terminal: HashSet<usize> = terminals.iter().copied().collect();
+    loop {
+        let mut degree = vec![0; len];
+        for i in &tree {
+            degree[graph.edges[*i].source] += 1;
+            degree[graph.edges[*i].target] += 1;
+        }
+        let is_dangling = |v: usize| degree[v] == 1 && !is_terminal.contains(&v);
+        let before = tree.len();
+        tree.retain(|i| {
+            !is_dangling(graph.edges[*i].source) && !is_dangling(graph.edges[*i].target)
+        });
+        if tree.len() == before {
+            break;
+        }
+    }
+    tree.sort_unstable();
+    let mut vertexes: Vec<usize> = tree
+        .iter()
+        .flat_map(|i| [graph.edges[*i].source, graph.edges[*i].target])
+        .filter(|v| !is_terminal.contains(v))
+        .collect();
+    vertexes.sort_unstable();
+    vertexes.dedup();
+    SteinerTree {
+        edges: tree,
+        vertexes,
+    }
+}
+
+/// unknown ids are ignored
+#[wasm_bindgen]
+pub fn steiner_tree(graph: &Graph, terminals: Vec<String>) -> SteinerTree {
+    let terminals: Vec<usize> = terminals
+        .iter()
+        .filter_map(|id| graph.get_vertex_by_id(id))
+        .collect();
+    make_steiner_tree(graph, &terminals)
+}
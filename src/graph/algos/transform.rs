@@ -0,0 +1,328 @@
+use crate::graph::Graph;
+use std::collections::HashSet;
+use wasm_bindgen::prelude::*;
+
+/// every linked pair of the collapsed adjacency once, undirected pairs as (low, high), sorted
+fn arcs(graph: &Graph) -> Vec<(usize, usize, f32)> {
+    let mut arcs = all_arcs(graph);
+    arcs.retain(|(s, t, _)| graph.is_directed || s <= t);
+    arcs
+}
+
+/// every arc of the collapsed adjacency, undirected pairs in both directions, sorted
+fn all_arcs(graph: &Graph) -> Vec<(usize, usize, f32)> {
+    let mut arcs: Vec<(usize, usize, f32)> = graph
+        .neighbors_map
+        .iter()
+        .enumerate()
+        .flat_map(|(s, map)| map.iter().map(move |(t, w)| (s, *t, *w)))
+        .collect();
+    arcs.sort_unstable_by_key(|(s, t, _)| (*s, *t));
+    arcs
+}
+
+fn empty(is_directed: bool) -> Graph {
+    let mut graph = Graph::new();
+    graph.set_directed(is_directed);
+    graph
+}
+
+fn copy_vertexes(from: &Graph, to: &mut Graph, prefix: &str) {
+    for v in 0..from.vertexes.len() {
+        let position = from.get_vertex_position(v);
+        let id = format!("{}{}", prefix, from.vertexes[v].id);
+        to.add_vertex(&id, position[0], position[1], position[2]);
+    }
+}
+
+/// every edge reversed, parallel edges and attributes are kept. undirected graphs are copied
+pub fn make_transpose(graph: &Graph) -> Graph {
+    let mut result = empty(graph.is_directed);
+    result.is_multigraph = true;
+    result.collapse_policy = graph.collapse_policy;
+    copy_vertexes(graph, &mut result, "");
+    for edge in &graph.edges {
+        result.add_edge_with_base(edge.target, edge.source, edge.base_weight, edge.weight);
+    }
+    result.is_multigraph = graph.is_multigraph;
+    result.vertex_attributes = graph.vertex_attributes.clone();
+    result.edge_attributes = graph.edge_attributes.clone();
+    result
+}
+
+/// a weight 1 edge for every distinct pair that isn't linked, no self loops.
+/// vertex attributes are kept
+pub fn make_complement(graph: &Graph) -> Graph {
+    let mut result = empty(graph.is_directed);
+    copy_vertexes(graph, &mut result, "");
+    let len = graph.vertexes.len();
+    for s in 0..len {
+        let targets = if graph.is_directed {
+            0..len
+        } else {
+            s + 1..len
+        };
+        for t in targets {
+            if s != t && !graph.neighbors_map[s].contains_key(&t) {
+                result.add_edge_by_index(s, t, 1.);
+            }
+        }
+    }
+    result.vertex_attributes = graph.vertex_attributes.clone();
+    result
+}
+
+/// one vertex per edge, its id is the edge id and it sits in the middle of the edge.
+/// two vertexes are linked when their edges share an end, for directed graphs when the first edge
+/// ends where the second starts. the edge attributes become the vertex attributes
+pub fn make_line_graph(graph: &Graph) -> Graph {
+    let mut result = empty(graph.is_directed);
+    for edge in &graph.edges {
+        let a = graph.get_vertex_position(edge.source);
+        let b = graph.get_vertex_position(edge.target);
+        result.add_vertex(
+            &edge.id.to_string(),
+            (a[0] + b[0]) / 2.,
+            (a[1] + b[1]) / 2.,
+            (a[2] + b[2]) / 2.,
+        );
+    }
+    let mut incident = vec![vec![]; graph.vertexes.len()];
+    for (index, edge) in graph.edges.iter().enumerate() {
+        incident[edge.source].push(index);
+        if !graph.is_directed && edge.source != edge.target {
+            incident[edge.target].push(index);
+        }
+    }
+    for (index, edge) in graph.edges.iter().enumerate() {
+        if graph.is_directed {
+            for next in &incident[edge.target] {
+                if *next != index {
+                    result.add_edge_by_index(index, *next, 1.);
+                }
+            }
+        } else {
+            for end in [edge.source, edge.target] {
+                for other in &incident[end] {
+                    if *other > index {
+                        result.add_edge_by_index(index, *other, 1.);
+                    }
+                }
+            }
+        }
+    }
+    result.vertex_attributes = graph.edge_attributes.clone();
+    result
+}
+
+/// the vertexes of `a` then the vertexes of `b`, ids get the prefixes so they stay unique.
+/// the result takes the direction of `a`, vertex attributes are kept and edge attributes dropped
+pub fn make_disjoint_union(a: &Graph, b: &Graph, a_prefix: &str, b_prefix: &str) -> Graph {
+    let mut result = empty(a.is_directed);
+    copy_vertexes(a, &mut result, a_prefix);
+    copy_vertexes(b, &mut result, b_prefix);
+    let offset = a.vertexes.len();
+    for (s, t, w) in arcs(a) {
+        result.add_edge_by_index(s, t, w);
+    }
+    for (s, t, w) in arcs(b) {
+        result.add_edge_by_index(s + offset, t + offset, w);
+    }
+    result.vertex_attributes.paste(0, &a.vertex_attributes);
+    result.vertex_attributes.paste(offset, &b.vertex_attributes);
+    result
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SetOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+/// vertexes and edges are matched by vertex id, `b` is read with the direction of `a`.
+/// edges keep the weight of `a` when both graphs have them. vertex attributes come from the graph
+/// the vertex is taken from, `a` first. edge attributes are dropped, edges are matched per vertex
+/// pair and parallel edges are collapsed
+fn make_set_operation(a: &Graph, b: &Graph, operation: SetOperation) -> Graph {
+    let mut result = empty(a.is_directed);
+    let from_a: Vec<usize> = (0..a.vertexes.len())
+        .filter(|v| {
+            operation != SetOperation::Intersection
+                || b.vertexs_map.contains_key(&a.vertexes[*v].id)
+        })
+        .collect();
+    let from_b: Vec<usize> = match operation {
+        SetOperation::Union => (0..b.vertexes.len())
+            .filter(|v| !a.vertexs_map.contains_key(&b.vertexes[*v].id))
+            .collect(),
+        _ => vec![],
+    };
+    for (graph, kept) in [(a, &from_a), (b, &from_b)] {
+        for v in kept.iter() {
+            let position = graph.get_vertex_position(*v);
+            result.add_vertex(
+                &graph.vertexes[*v].id,
+                position[0],
+                position[1],
+                position[2],
+            );
+        }
+    }
+    result
+        .vertex_attributes
+        .paste(0, &a.vertex_attributes.select(&from_a));
+    result
+        .vertex_attributes
+        .paste(from_a.len(), &b.vertex_attributes.select(&from_b));
+    // the same pair of `a` in `b` as indexes of `b`
+    let in_b = |s: usize, t: usize| -> bool {
+        match (
+            b.vertexs_map.get(&a.vertexes[s].id),
+            b.vertexs_map.get(&a.vertexes[t].id),
+        ) {
+            (Some(bs), Some(bt)) => b.neighbors_map[*bs].contains_key(bt),
+            _ => false,
+        }
+    };
+    let index = |result: &Graph, graph: &Graph, v: usize| result.vertexs_map[&graph.vertexes[v].id];
+    for (s, t, w) in arcs(a) {
+        let keep = match operation {
+            SetOperation::Union => true,
+            SetOperation::Intersection => in_b(s, t),
+            SetOperation::Difference => !in_b(s, t),
+        };
+        if keep {
+            let (s, t) = (index(&result, a, s), index(&result, a, t));
+            result.add_edge_by_index(s, t, w);
+        }
+    }
+    if operation == SetOperation::Union {
+        let mut linked: HashSet<(usize, usize)> = HashSet::new();
+        for (s, map) in result.neighbors_map.iter().enumerate() {
+            linked.extend(map.keys().map(|t| (s, *t)));
+        }
+        for (s, t, w) in arcs(b) {
+            let (s, t) = (index(&result, b, s), index(&result, b, t));
+            if !linked.contains(&(s, t)) {
+                result.add_edge_by_index(s, t, w);
+            }
+        }
+    }
+    result
+}
+
+/// every vertex and edge of either graph
+pub fn make_union(a: &Graph, b: &Graph) -> Graph {
+    make_set_operation(a, b, SetOperation::Union)
+}
+
+/// the vertexes in both graphs and the edges in both graphs
+pub fn make_intersection(a: &Graph, b: &Graph) -> Graph {
+    make_set_operation(a, b, SetOperation::Intersection)
+}
+
+/// the vertexes of `a` and its edges that are not in `b`
+pub fn make_difference(a: &Graph, b: &Graph) -> Graph {
+    make_set_operation(a, b, SetOperation::Difference)
+}
+
+/// vertex `(u, v)` gets the id "u,v" and the sum of both positions, index `u * b_len + v`.
+/// the result is directed when either factor is, then undirected factors link both ways
+fn make_product_vertexes(a: &Graph, b: &Graph) -> Graph {
+    let mut result = empty(a.is_directed || b.is_directed);
+    for u in 0..a.vertexes.len() {
+        let pu = a.get_vertex_position(u);
+        for v in 0..b.vertexes.len() {
+            let pv = b.get_vertex_position(v);
+            let id = format!("{},{}", a.vertexes[u].id, b.vertexes[v].id);
+            result.add_vertex(&id, pu[0] + pv[0], pu[1] + pv[1], pu[2] + pv[2]);
+        }
+    }
+    result
+}
+
+/// every undirected pair shows up from both ends, add it once
+fn add_product_arc(result: &mut Graph, source: usize, target: usize, weight: f32) {
+    if result.is_directed || source <= target {
+        result.add_edge_by_index(source, target, weight);
+    }
+}
+
+/// `(u, v)` and `(u', v')` are linked when u = u' and v links v', or v = v' and u links u'.
+/// the edge keeps the weight of the factor edge, O(|Ea| |Vb| + |Va| |Eb|)
+pub fn make_cartesian_product(a: &Graph, b: &Graph) -> Graph {
+    let mut result = make_product_vertexes(a, b);
+    let b_len = b.vertexes.len();
+    for (su, tu, weight) in all_arcs(a) {
+        for v in 0..b_len {
+            add_product_arc(&mut result, su * b_len + v, tu * b_len + v, weight);
+        }
+    }
+    let b_arcs = all_arcs(b);
+    for u in 0..a.vertexes.len() {
+        for (sv, tv, weight) in &b_arcs {
+            add_product_arc(&mut result, u * b_len + sv, u * b_len + tv, *weight);
+        }
+    }
+    result
+}
+
+/// `(u, v)` and `(u', v')` are linked when u links u' and v links v', weights are multiplied.
+/// O(|Ea| |Eb|)
+pub fn make_tensor_product(a: &Graph, b: &Graph) -> Graph {
+    let mut result = make_product_vertexes(a, b);
+    let b_len = b.vertexes.len();
+    let b_arcs = all_arcs(b);
+    for (su, tu, wa) in all_arcs(a) {
+        for (sv, tv, wb) in &b_arcs {
+            add_product_arc(&mut result, su * b_len + sv, tu * b_len + tv, wa * wb);
+        }
+    }
+    result
+}
+
+#[wasm_bindgen]
+pub fn transpose(graph: &Graph) -> Graph {
+    make_transpose(graph)
+}
+
+#[wasm_bindgen]
+pub fn complement(graph: &Graph) -> Graph {
+    make_complement(graph)
+}
+
+#[wasm_bindgen]
+pub fn line_graph(graph: &Graph) -> Graph {
+    make_line_graph(graph)
+}
+
+#[wasm_bindgen]
+pub fn disjoint_union(a: &Graph, b: &Graph, a_prefix: &str, b_prefix: &str) -> Graph {
+    make_disjoint_union(a, b, a_prefix, b_prefix)
+}
+
+#[wasm_bindgen]
+pub fn graph_union(a: &Graph, b: &Graph) -> Graph {
+    make_union(a, b)
+}
+
+#[wasm_bindgen]
+pub fn graph_intersection(a: &Graph, b: &Graph) -> Graph {
+    make_intersection(a, b)
+}
+
+#[wasm_bindgen]
+pub fn graph_difference(a: &Graph, b: &Graph) -> Graph {
+    make_difference(a, b)
+}
+
+#[wasm_bindgen]
+pub fn cartesian_product(a: &Graph, b: &Graph) -> Graph {
+    make_cartesian_product(a, b)
+}
+
+#[wasm_bindgen]
+pub fn tensor_product(a: &Graph, b: &Graph) -> Graph {
+    make_tensor_product(a, b)
+}
@@ -0,0 +1,276 @@
+use crate::graph::{algos::sssp, Graph};
+use wasm_bindgen::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TspDistance {
+    /// shortest path length in the graph
+    ShortestPath,
+    /// straight line between the current layout positions
+    Euclidean,
+}
+
+impl TspDistance {
+    pub fn from_name(name: &str) -> Option<TspDistance> {
+        match name {
+            "shortest_path" | "graph" => Some(TspDistance::ShortestPath),
+            "euclidean" | "position" | "" => Some(TspDistance::Euclidean),
+            _ => None,
+        }
+    }
+}
+
+/// symmetric distances between `vertexes`, unreachable pairs get a penalty larger than any finite tour
+pub fn make_distance_matrix(graph: &Graph, vertexes: &[usize], kind: TspDistance) -> Vec<Vec<f32>> {
+    let len = vertexes.len();
+    let mut dist = vec![vec![0.; len]; len];
+    match kind {
+        TspDistance::Euclidean => {
+            let positions: Vec<Vec<f32>> = vertexes
+                .iter()
+                .map(|v| graph.get_vertex_position(*v))
+                .collect();
+            for i in 0..len {
+                for j in 0..len {
+                    let (a, b) = (&positions[i], &positions[j]);
+                    dist[i][j] =
+                        ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2))
+                            .sqrt();
+                }
+            }
+        }
+        TspDistance::ShortestPath => {
+            let csr = graph.snapshot();
+            let negative = sssp::has_negative_weight(&csr);
+            for (i, source) in vertexes.iter().enumerate() {
+                let paths = if negative {
+                    sssp::make_bellman_ford(&csr, *source)
+                } else {
+                    sssp::make_dijkstra(&csr, &[*source])
+                };
+                for (j, target) in vertexes.iter().enumerate() {
+                    dist[i][j] = paths.dist[*target];
+                }
+            }
+            let max = dist
+                .iter()
+                .flatten()
+                .filter(|d| d.is_finite())
+                .fold(0., |m: f32, d| m.max(d.abs()));
+            let penalty = (max + 1.) * len as f32;
+            dist.iter_mut()
+                .flatten()
+                .filter(|d| !d.is_finite())
+                .for_each(|d| *d = penalty);
+            // directed graphs give different lengths per direction, tours use their mean
+            dist = (0..len)
+                .map(|i| (0..len).map(|j| (dist[i][j] + dist[j][i]) / 2.).collect())
+                .collect();
+        }
+    }
+    dist
+}
+
+/// length of a tour of matrix indexes, `closed` adds the way back to the start
+pub fn tour_length(dist: &[Vec<f32>], tour: &[usize], closed: bool) -> f32 {
+    let mut length: f32 = tour.windows(2).map(|w| dist[w[0]][w[1]]).sum();
+    if closed && tour.len() > 1 {
+        length += dist[tour[tour.len() - 1]][tour[0]];
+    }
+    length
+}
+
+/// always go to the closest unvisited vertex, starting from matrix index 0
+pub fn make_nearest_neighbor_tour(dist: &[Vec<f32>]) -> Vec<usize> {
+    let len = dist.len();
+    if len == 0 {
+        return vec![];
+    }
+    let mut visited = vec![false; len];
+    let mut tour = vec![0];
+    visited[0] = true;
+    while tour.len() < len {
+        let now = tour[tour.len() - 1];
+        let next = (0..len)
+            .filter(|v| !visited[*v])
+            .min_by(|a, b| dist[now][*a].total_cmp(&dist[now][*b]))
+            .unwrap();
+        visited[next] = true;
+        tour.push(next);
+    }
+    tour
+}
+
+/// preorder walk of a prim mst rooted at matrix index 0, at most twice the optimal closed tour
+/// for metric distances
+pub fn make_mst_tour(dist: &[Vec<f32>]) -> Vec<usize> {
+    let len = dist.len();
+    if len == 0 {
+        return vec![];
+    }
+    let mut in_tree = vec![false; len];
+    let mut best = vec![f32::INFINITY; len];
+    let mut parent = vec![usize::MAX; len];
+    let mut children = vec![vec![]; len];
+    best[0] = 0.;
+    for _ in 0..len {
+        let u = (0..len)
+            .filter(|v| !in_tree[*v])
+            .min_by(|a, b| best[*a].total_cmp(&best[*b]))
+            .unwrap();
+        in_tree[u] = true;
+        if parent[u] != usize::MAX {
+            children[parent[u]].push(u);
+        }
+        for v in 0..len {
+            if !in_tree[v] && dist[u][v] < best[v] {
+                best[v] = dist[u][v];
+                parent[v] = u;
+            }
+        }
+    }
+    let mut tour = Vec::with_capacity(len);
+    let mut stack = vec![0];
+    while let Some(u) = stack.pop() {
+        tour.push(u);
+        stack.extend(children[u].iter().rev());
+    }
+    tour
+}
+
+/// cost of the edge a -> b where b may be missing at the end of an open tour
+fn cost(dist: &[Vec<f32>], a: usize, b: Option<usize>) -> f32 {
+    b.map(|b| dist[a][b]).unwrap_or(0.)
+}
+
+/// the vertex after position `i`, wrapping to the start for closed tours
+fn after(tour: &[usize], i: usize, closed: bool) -> Option<usize> {
+    if i + 1 < tour.len() {
+        Some(tour[i + 1])
+    } else if closed {
+        Some(tour[0])
+    } else {
+        None
+    }
+}
+
+/// reverse segments while it shortens the tour, the first vertex never moves
+pub fn improve_2opt(dist: &[Vec<f32>], tour: &mut [usize], closed: bool) -> bool {
+    let len = tour.len();
+    let mut improved = false;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in 1..len {
+            for j in (i + 1)..len {
+                let prev = tour[i - 1];
+                let next = after(tour, j, closed);
+                let delta = dist[prev][tour[j]] + cost(dist, tour[i], next)
+                    - dist[prev][tour[i]]
+                    - cost(dist, tour[j], next);
+                if delta < -1e-6 {
+                    tour[i..=j].reverse();
+                    changed = true;
+                    improved = true;
+                }
+            }
+        }
+    }
+    improved
+}
+
+/// move segments of 1 to 3 vertexes (optionally reversed) to a better place, the first vertex never moves
+pub fn improve_or_opt(dist: &[Vec<f32>], tour: &mut Vec<usize>, closed: bool) -> bool {
+    let len = tour.len();
+    let mut improved = false;
+    let mut changed = true;
+    while changed {
+        changed = false;
+        'search: for seg_len in 1..=3 {
+            for i in 1..len {
+                if i + seg_len > len {
+                    break;
+                }
+                let (first, last) = (tour[i], tour[i + seg_len - 1]);
+                let prev = tour[i - 1];
+                let next = after(tour, i + seg_len - 1, closed);
+                let gain = dist[prev][first] + cost(dist, last, next) - cost(dist, prev, next);
+                let rest: Vec<usize> = tour[..i]
+                    .iter()
+                    .chain(&tour[(i + seg_len)..])
+                    .copied()
+                    .collect();
+                for k in 0..rest.len() {
+                    let a = rest[k];
+                    let b = after(&rest, k, closed);
+                    let base = cost(dist, a, b);
+                    let forward = dist[a][first] + cost(dist, last, b) - base;
+                    let backward = dist[a][last] + cost(dist, first, b) - base;
+                    if forward.min(backward) < gain - 1e-6 {
+                        let mut segment: Vec<usize> = tour[i..(i + seg_len)].to_vec();
+                        if backward < forward {
+                            segment.reverse();
+                        }
+                        let mut next_tour = rest[..=k].to_vec();
+                        next_tour.extend(segment);
+                        next_tour.extend(&rest[(k + 1)..]);
+                        *tour = next_tour;
+                        changed = true;
+                        improved = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+    }
+    improved
+}
+
+/// construct with `construction` ("nearest_neighbor" or "mst") and improve with 2-opt and or-opt
+/// until neither helps. return the vertexes in visiting order, starting from `vertexes[0]`
+pub fn make_tour(
+    graph: &Graph,
+    vertexes: &[usize],
+    kind: TspDistance,
+    construction: &str,
+    closed: bool,
+) -> Vec<usize> {
+    let dist = make_distance_matrix(graph, vertexes, kind);
+    let mut tour = match construction {
+        "mst" => make_mst_tour(&dist),
+        _ => make_nearest_neighbor_tour(&dist),
+    };
+    loop {
+        let a = improve_2opt(&dist, &mut tour, closed);
+        let b = improve_or_opt(&dist, &mut tour, closed);
+        if !a && !b {
+            break;
+        }
+    }
+    tour.iter().map(|i| vertexes[*i]).collect()
+}
+
+/// return vertex indices in visiting order, an empty `ids` means every vertex. unknown ids are ignored
+#[wasm_bindgen]
+pub fn tsp_tour(
+    graph: &Graph,
+    ids: Vec<String>,
+    distance: &str,
+    construction: &str,
+    closed: bool,
+) -> Vec<u32> {
+    let kind = match TspDistance::from_name(distance) {
+        Some(kind) => kind,
+        None => return vec![],
+    };
+    let vertexes: Vec<usize> = if ids.is_empty() {
+        (0..graph.vertexes.len()).collect()
+    } else {
+        ids.iter()
+            .filter_map(|id| graph.get_vertex_by_id(id))
+            .collect()
+    };
+    make_tour(graph, &vertexes, kind, construction, closed)
+        .iter()
+        .map(|v| *v as u32)
+        .collect()
+}
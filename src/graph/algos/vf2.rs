@@ -0,0 +1,264 @@
+use crate::graph::{csr::Csr, Graph};
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MatchMode {
+    /// bijection that keeps edges and non edges, both graphs must have the same size
+    Isomorphism,
+    /// the pattern must be an induced subgraph of the target
+    InducedSubgraph,
+    /// every pattern edge must exist in the target, extra target edges are allowed
+    Monomorphism,
+}
+
+impl MatchMode {
+    pub fn from_name(name: &str) -> Option<MatchMode> {
+        match name {
+            "isomorphism" => Some(MatchMode::Isomorphism),
+            "subgraph" | "induced" => Some(MatchMode::InducedSubgraph),
+            "monomorphism" | "" => Some(MatchMode::Monomorphism),
+            _ => None,
+        }
+    }
+}
+
+fn has_edge(graph: &Graph, u: usize, v: usize) -> bool {
+    graph.neighbors_map[u].contains_key(&v)
+}
+
+struct State<'a, V, E> {
+    pattern: &'a Graph,
+    target: &'a Graph,
+    mode: MatchMode,
+    vertex_match: V,
+    edge_match: E,
+    /// pattern vertexes in matching order
+    order: Vec<usize>,
+    /// an earlier pattern vertex adjacent to order[i], and whether the edge goes out of it
+    parent: Vec<Option<(usize, bool)>>,
+    pattern_degree: Vec<(usize, usize)>,
+    target_degree: Vec<(usize, usize)>,
+    target_csr: Rc<Csr>,
+    core: Vec<usize>,
+    used: Vec<bool>,
+    limit: usize,
+    matches: Vec<Vec<usize>>,
+}
+
+impl<'a, V, E> State<'a, V, E>
+where
+    V: FnMut(usize, usize) -> bool,
+    E: FnMut((usize, usize), (usize, usize)) -> bool,
+{
+    fn is_feasible(&mut self, depth: usize, p: usize, t: usize) -> bool {
+        if self.used[t] {
+            return false;
+        }
+        let (p_out, p_in) = self.pattern_degree[p];
+        let (t_out, t_in) = self.target_degree[t];
+        let degree_ok = match self.mode {
+            MatchMode::Isomorphism => p_out == t_out && p_in == t_in,
+            _ => p_out <= t_out && p_in <= t_in,
+        };
+        if !degree_ok || !(self.vertex_match)(p, t) {
+            return false;
+        }
+        // the new pair against itself for self loops, then against every mapped pair
+        for i in 0..=depth {
+            let (q, s) = if i == depth {
+                (p, t)
+            } else {
+                (self.order[i], self.core[self.order[i]])
+            };
+            for ((a, b), (c, d)) in [((p, q), (t, s)), ((q, p), (s, t))] {
+                let pattern_edge = has_edge(self.pattern, a, b);
+                let target_edge = has_edge(self.target, c, d);
+                if pattern_edge && (!target_edge || !(self.edge_match)((a, b), (c, d))) {
+                    return false;
+                }
+                if !pattern_edge && target_edge && self.mode != MatchMode::Monomorphism {
+                    return false;
+                }
+                if a == b {
+                    break;
+                }
+            }
+        }
+        true
+    }
+
+    fn search(&mut self, depth: usize) {
+        if depth == self.order.len() {
+            let mapping = self.core.clone();
+            self.matches.push(mapping);
+            return;
+        }
+        let p = self.order[depth];
+        let candidates: Vec<usize> = match self.parent[depth] {
+            Some((q, true)) => self.target_csr.out_neighbors(self.core[q]).to_vec(),
+            Some((q, false)) => self.target_csr.in_neighbors(self.core[q]).to_vec(),
+            None => (0..self.target.vertexes.len()).collect(),
+        };
+        for t in candidates {
+            if self.limit > 0 && self.matches.len() >= self.limit {
+                return;
+            }
+            if !self.is_feasible(depth, p, t) {
+                continue;
+            }
+            self.core[p] = t;
+            self.used[t] = true;
+            self.search(depth + 1);
+            self.used[t] = false;
+            self.core[p] = usize::MAX;
+        }
+    }
+}
+
+/// the next vertex is the one with most links into the ordered set, then the one with largest degree,
+/// so candidates can always be taken from the neighbors of an already mapped vertex
+fn make_order(
+    pattern: &Graph,
+    csr: &Csr,
+    degree: &[(usize, usize)],
+) -> (Vec<usize>, Vec<Option<(usize, bool)>>) {
+    let len = pattern.vertexes.len();
+    let mut ordered = vec![false; len];
+    let mut links = vec![0; len];
+    let mut order = Vec::with_capacity(len);
+    let mut parent = Vec::with_capacity(len);
+    while order.len() < len {
+        let next = (0..len)
+            .filter(|v| !ordered[*v])
+            .max_by_key(|v| (links[*v], degree[*v].0 + degree[*v].1, len - *v))
+            .unwrap();
+        let link = order.iter().find_map(|q| {
+            if has_edge(pattern, *q, next) {
+                Some((*q, true))
+            } else if has_edge(pattern, next, *q) {
+                Some((*q, false))
+            } else {
+                None
+            }
+        });
+        ordered[next] = true;
+        order.push(next);
+        parent.push(link);
+        for v in csr.out_neighbors(next).iter().chain(csr.in_neighbors(next)) {
+            links[*v] += 1;
+        }
+    }
+    (order, parent)
+}
+
+fn make_degree(csr: &Csr) -> Vec<(usize, usize)> {
+    (0..csr.len())
+        .map(|v| (csr.out_degree(v), csr.in_degree(v)))
+        .collect()
+}
+
+/// vf2 style backtracking, every match maps pattern index i to target index `match[i]`.
+/// `vertex_match(pattern_index, target_index)` and `edge_match((pattern_src, pattern_dst), (target_src, target_dst))`
+/// can reject pairs, `limit` 0 means no limit
+pub fn find_matches<V, E>(
+    pattern: &Graph,
+    target: &Graph,
+    mode: MatchMode,
+    limit: usize,
+    vertex_match: V,
+    edge_match: E,
+) -> Vec<Vec<usize>>
+where
+    V: FnMut(usize, usize) -> bool,
+    E: FnMut((usize, usize), (usize, usize)) -> bool,
+{
+    let (pattern_len, target_len) = (pattern.vertexes.len(), target.vertexes.len());
+    let size_ok = match mode {
+        MatchMode::Isomorphism => pattern_len == target_len,
+        _ => pattern_len <= target_len,
+    };
+    if !size_ok || pattern_len == 0 {
+        return vec![];
+    }
+    let pattern_csr = pattern.snapshot();
+    let target_csr = target.snapshot();
+    let pattern_degree = make_degree(&pattern_csr);
+    let (order, parent) = make_order(pattern, &pattern_csr, &pattern_degree);
+    let mut state = State {
+        pattern,
+        target,
+        mode,
+        vertex_match,
+        edge_match,
+        order,
+        parent,
+        pattern_degree,
+        target_degree: make_degree(&target_csr),
+        target_csr,
+        core: vec![usize::MAX; pattern_len],
+        used: vec![false; target_len],
+        limit,
+        matches: vec![],
+    };
+    state.search(0);
+    state.matches
+}
+
+/// return the matches as a flat array of [pattern_id, target_id, ...], every match takes
+/// 2 * pattern length items. `vertex_match(pattern_id, target_id)` and
+/// `edge_match(pattern_src, pattern_dst, target_src, target_dst)` are optional js predicates
+#[wasm_bindgen]
+pub fn find_subgraph_matches(
+    pattern: &Graph,
+    target: &Graph,
+    mode: &str,
+    limit: usize,
+    vertex_match: Option<js_sys::Function>,
+    edge_match: Option<js_sys::Function>,
+) -> Vec<String> {
+    let mode = match MatchMode::from_name(mode) {
+        Some(mode) => mode,
+        None => return vec![],
+    };
+    let pattern_id = |i: usize| JsValue::from_str(&pattern.vertexes[i].id);
+    let target_id = |i: usize| JsValue::from_str(&target.vertexes[i].id);
+    let matches = find_matches(
+        pattern,
+        target,
+        mode,
+        limit,
+        |p, t| match &vertex_match {
+            Some(f) => f
+                .call2(&JsValue::NULL, &pattern_id(p), &target_id(t))
+                .map(|v| v.is_truthy())
+                .unwrap_or(false),
+            None => true,
+        },
+        |(a, b), (c, d)| match &edge_match {
+            Some(f) => {
+                let args = js_sys::Array::of4(
+                    &pattern_id(a),
+                    &pattern_id(b),
+                    &target_id(c),
+                    &target_id(d),
+                );
+                f.apply(&JsValue::NULL, &args)
+                    .map(|v| v.is_truthy())
+                    .unwrap_or(false)
+            }
+            None => true,
+        },
+    );
+    matches
+        .iter()
+        .flat_map(|mapping| {
+            mapping.iter().enumerate().flat_map(|(p, t)| {
+                [
+                    pattern.vertexes[p].id.clone(),
+                    target.vertexes[*t].id.clone(),
+                ]
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,382 @@
+use crate::graph::Graph;
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+/// one value per vertex or edge, None where it was never set
+#[derive(Clone, Debug)]
+pub enum Column {
+    String(Vec<Option<String>>),
+    Number(Vec<Option<f64>>),
+    Bool(Vec<Option<bool>>),
+    Numbers(Vec<Option<Vec<f64>>>),
+}
+
+impl Column {
+    fn push_none(&mut self) {
+        match self {
+            Column::String(values) => values.push(None),
+            Column::Number(values) => values.push(None),
+            Column::Bool(values) => values.push(None),
+            Column::Numbers(values) => values.push(None),
+        }
+    }
+
+    fn swap_remove(&mut self, index: usize) {
+        match self {
+            Column::String(values) => {
+                values.swap_remove(index);
+            }
+            Column::Number(values) => {
+                values.swap_remove(index);
+            }
+            Column::Bool(values) => {
+                values.swap_remove(index);
+            }
+            Column::Numbers(values) => {
+                values.swap_remove(index);
+            }
+        }
+    }
+
+    /// copy `other` over the values from `start`, false when the types differ
+    fn paste(&mut self, start: usize, other: &Column) -> bool {
+        fn copy<T: Clone>(values: &mut [Option<T>], start: usize, other: &[Option<T>]) {
+            values[start..start + other.len()].clone_from_slice(other);
+        }
+        match (self, other) {
+            (Column::String(values), Column::String(other)) => copy(values, start, other),
+            (Column::Number(values), Column::Number(other)) => copy(values, start, other),
+            (Column::Bool(values), Column::Bool(other)) => copy(values, start, other),
+            (Column::Numbers(values), Column::Numbers(other)) => copy(values, start, other),
+            _ => return false,
+        }
+        true
+    }
+
+    fn empty_like(&self, len: usize) -> Column {
+        match self {
+            Column::String(_) => Column::String(vec![None; len]),
+            Column::Number(_) => Column::Number(vec![None; len]),
+            Column::Bool(_) => Column::Bool(vec![None; len]),
+            Column::Numbers(_) => Column::Numbers(vec![None; len]),
+        }
+    }
+
+    /// a hashable key of the value, used to put vertexes with equal values in one group
+    pub fn key(&self, index: usize) -> Option<String> {
+        match self {
+            Column::String(values) => values[index].clone(),
+            Column::Number(values) => values[index].map(|v| v.to_string()),
+            Column::Bool(values) => values[index].map(|v| v.to_string()),
+            Column::Numbers(values) => values[index].as_ref().map(|v| format!("{:?}", v)),
+        }
+    }
+}
+
+/// named columns that stay the same length as the vertexes or edges they describe
+#[derive(Clone, Debug, Default)]
+pub struct Attributes {
+    len: usize,
+    columns: HashMap<String, Column>,
+}
+
+macro_rules! typed_column {
+    ($get:ident, $set:ident, $variant:ident, $ty:ty) => {
+        pub fn $get(&self, name: &str, index: usize) -> Option<$ty> {
+            match self.columns.get(name)? {
+                Column::$variant(values) => values.get(index)?.clone(),
+                _ => None,
+            }
+        }
+
+        /// a column of another type with the same name is replaced. return false and write
+        /// nothing when the index is out of range
+        pub fn $set(&mut self, name: &str, index: usize, value: $ty) -> bool {
+            let len = self.len;
+            if index >= len {
+                return false;
+            }
+            let column = self
+                .columns
+                .entry(name.to_string())
+                .or_insert_with(|| Column::$variant(vec![None; len]));
+            if !matches!(column, Column::$variant(_)) {
+                *column = Column::$variant(vec![None; len]);
+            }
+            if let Column::$variant(values) = column {
+                values[index] = Some(value);
+            }
+            true
+        }
+    };
+}
+
+impl Attributes {
+    typed_column!(get_string, set_string, String, String);
+    typed_column!(get_number, set_number, Number, f64);
+    typed_column!(get_bool, set_bool, Bool, bool);
+    typed_column!(get_numbers, set_numbers, Numbers, Vec<f64>);
+
+    pub fn push(&mut self) {
+        self.len += 1;
+        self.columns
+            .values_mut()
+            .for_each(|column| column.push_none());
+    }
+
+    pub fn swap_remove(&mut self, index: usize) {
+        self.len -= 1;
+        self.columns
+            .values_mut()
+            .for_each(|column| column.swap_remove(index));
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.columns.clear();
+    }
+
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.get(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.columns.remove(name).is_some()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.columns.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// NaN where the value is missing, None when there is no number column of this name
+    pub fn number_column(&self, name: &str) -> Option<Vec<f64>> {
+        match self.columns.get(name)? {
+            Column::Number(values) => Some(values.iter().map(|v| v.unwrap_or(f64::NAN)).collect()),
+            _ => None,
+        }
+    }
+
+    /// NaN values are stored as missing
+    pub fn set_number_column(&mut self, name: &str, values: &[f64]) {
+        let column = (0..self.len)
+            .map(|i| values.get(i).copied().filter(|v| !v.is_nan()))
+            .collect();
+        self.columns
+            .insert(name.to_string(), Column::Number(column));
+    }
+
+    /// the rows at `indexes` in that order, for subgraphs
+    pub fn select(&self, indexes: &[usize]) -> Attributes {
+        let pick = |column: &Column| match column {
+            Column::String(values) => {
+                Column::String(indexes.iter().map(|i| values[*i].clone()).collect())
+            }
+            Column::Number(values) => Column::Number(indexes.iter().map(|i| values[*i]).collect()),
+            Column::Bool(values) => Column::Bool(indexes.iter().map(|i| values[*i]).collect()),
+            Column::Numbers(values) => {
+                Column::Numbers(indexes.iter().map(|i| values[*i].clone()).collect())
+            }
+        };
+        Attributes {
+            len: indexes.len(),
+            columns: self
+                .columns
+                .iter()
+                .map(|(name, column)| (name.clone(), pick(column)))
+                .collect(),
+        }
+    }
+
+    /// write the rows of `other` from row `start` on, like `select` in reverse.
+    /// a column of another type with the same name is kept as it is, the names of those
+    /// skipped columns are returned
+    pub fn paste(&mut self, start: usize, other: &Attributes) -> Vec<String> {
+        let len = self.len;
+        let mut skipped = vec![];
+        for (name, from) in &other.columns {
+            let column = self
+                .columns
+                .entry(name.clone())
+                .or_insert_with(|| from.empty_like(len));
+            if !column.paste(start, from) {
+                skipped.push(name.clone());
+            }
+        }
+        skipped
+    }
+
+    /// number every distinct value in the order it first appears, missing values share a group.
+    /// None when there is no column of this name
+    pub fn groups(&self, name: &str) -> Option<Vec<usize>> {
+        let column = self.columns.get(name)?;
+        let mut ids: HashMap<Option<String>, usize> = HashMap::new();
+        Some(
+            (0..self.len)
+                .map(|i| {
+                    let next = ids.len();
+                    *ids.entry(column.key(i)).or_insert(next)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Graph {
+    /// run `f` with a number column as the edge weights like `use_edge_weight`, the weights in use
+    /// before come back afterwards. None when there is no number column of this name
+    pub fn with_edge_weight<T>(&mut self, name: &str, f: impl FnOnce(&Graph) -> T) -> Option<T> {
+        let weights: Vec<f32> = self.edges.iter().map(|edge| edge.weight).collect();
+        if !self.use_edge_weight(name) {
+            return None;
+        }
+        let result = f(self);
+        for (edge, weight) in self.edges.iter_mut().zip(weights) {
+            edge.weight = weight;
+        }
+        self.refresh_weights();
+        Some(result)
+    }
+}
+
+#[wasm_bindgen]
+impl Graph {
+    pub fn get_vertex_string(&self, name: &str, index: usize) -> Option<String> {
+        self.vertex_attributes.get_string(name, index)
+    }
+
+    pub fn set_vertex_string(&mut self, name: &str, index: usize, value: String) -> bool {
+        self.vertex_attributes.set_string(name, index, value)
+    }
+
+    pub fn get_vertex_number(&self, name: &str, index: usize) -> Option<f64> {
+        self.vertex_attributes.get_number(name, index)
+    }
+
+    pub fn set_vertex_number(&mut self, name: &str, index: usize, value: f64) -> bool {
+        self.vertex_attributes.set_number(name, index, value)
+    }
+
+    pub fn get_vertex_bool(&self, name: &str, index: usize) -> Option<bool> {
+        self.vertex_attributes.get_bool(name, index)
+    }
+
+    pub fn set_vertex_bool(&mut self, name: &str, index: usize, value: bool) -> bool {
+        self.vertex_attributes.set_bool(name, index, value)
+    }
+
+    pub fn get_vertex_numbers(&self, name: &str, index: usize) -> Option<Vec<f64>> {
+        self.vertex_attributes.get_numbers(name, index)
+    }
+
+    pub fn set_vertex_numbers(&mut self, name: &str, index: usize, value: Vec<f64>) -> bool {
+        self.vertex_attributes.set_numbers(name, index, value)
+    }
+
+    pub fn get_edge_string(&self, name: &str, index: usize) -> Option<String> {
+        self.edge_attributes.get_string(name, index)
+    }
+
+    pub fn set_edge_string(&mut self, name: &str, index: usize, value: String) -> bool {
+        self.edge_attributes.set_string(name, index, value)
+    }
+
+    pub fn get_edge_number(&self, name: &str, index: usize) -> Option<f64> {
+        self.edge_attributes.get_number(name, index)
+    }
+
+    pub fn set_edge_number(&mut self, name: &str, index: usize, value: f64) -> bool {
+        self.edge_attributes.set_number(name, index, value)
+    }
+
+    pub fn get_edge_bool(&self, name: &str, index: usize) -> Option<bool> {
+        self.edge_attributes.get_bool(name, index)
+    }
+
+    pub fn set_edge_bool(&mut self, name: &str, index: usize, value: bool) -> bool {
+        self.edge_attributes.set_bool(name, index, value)
+    }
+
+    pub fn get_edge_numbers(&self, name: &str, index: usize) -> Option<Vec<f64>> {
+        self.edge_attributes.get_numbers(name, index)
+    }
+
+    pub fn set_edge_numbers(&mut self, name: &str, index: usize, value: Vec<f64>) -> bool {
+        self.edge_attributes.set_numbers(name, index, value)
+    }
+
+    /// one value per vertex, NaN where it is missing. empty when there is no number column of this name
+    pub fn get_vertex_number_column(&self, name: &str) -> Vec<f64> {
+        self.vertex_attributes
+            .number_column(name)
+            .unwrap_or_default()
+    }
+
+    pub fn set_vertex_number_column(&mut self, name: &str, values: Vec<f64>) {
+        self.vertex_attributes.set_number_column(name, &values);
+    }
+
+    /// one value per edge in adding order, NaN where it is missing
+    pub fn get_edge_number_column(&self, name: &str) -> Vec<f64> {
+        self.edge_attributes.number_column(name).unwrap_or_default()
+    }
+
+    pub fn set_edge_number_column(&mut self, name: &str, values: Vec<f64>) {
+        self.edge_attributes.set_number_column(name, &values);
+    }
+
+    pub fn get_vertex_attribute_names(&self) -> Vec<String> {
+        self.vertex_attributes.names()
+    }
+
+    pub fn get_edge_attribute_names(&self) -> Vec<String> {
+        self.edge_attributes.names()
+    }
+
+    pub fn remove_vertex_attribute(&mut self, name: &str) -> bool {
+        self.vertex_attributes.remove(name)
+    }
+
+    pub fn remove_edge_attribute(&mut self, name: &str) -> bool {
+        self.edge_attributes.remove(name)
+    }
+
+    /// group index of every vertex by the value of an attribute of any type, see `Attributes::groups`
+    pub fn get_vertex_groups(&self, name: &str) -> Vec<u32> {
+        self.vertex_attributes
+            .groups(name)
+            .unwrap_or_default()
+            .iter()
+            .map(|g| *g as u32)
+            .collect()
+    }
+
+    /// copy a number column into the edge weights every algorithm reads, missing values fall back
+    /// to the weight the edge was added with. parallel edges are collapsed by the collapse policy,
+    /// `reset_edge_weight` switches back. return false when there is no number column of this name
+    pub fn use_edge_weight(&mut self, name: &str) -> bool {
+        let weights = match self.edge_attributes.number_column(name) {
+            Some(weights) => weights,
+            None => return false,
+        };
+        for (edge, weight) in self.edges.iter_mut().zip(weights) {
+            let weight = weight as f32;
+            edge.weight = if weight.is_finite() && weight != 0. {
+                weight
+            } else {
+                edge.base_weight
+            };
+        }
+        self.refresh_weights();
+        true
+    }
+
+    /// back to the weights the edges were added with
+    pub fn reset_edge_weight(&mut self) {
+        for edge in self.edges.iter_mut() {
+            edge.weight = edge.base_weight;
+        }
+        self.refresh_weights();
+    }
+}
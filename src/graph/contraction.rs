@@ -0,0 +1,209 @@
+use crate::graph::{attributes::Attributes, Graph, GraphRemoval};
+use std::collections::{BTreeMap, HashMap};
+use wasm_bindgen::prelude::*;
+
+/// what `Graph::contract` removed, enough to put the members back with `Graph::expand`
+#[wasm_bindgen]
+pub struct Contraction {
+    meta_id: String,
+    ids: Vec<String>,
+    /// 3 per member, relative to the meta vertex
+    offsets: Vec<f32>,
+    vertex_rows: Attributes,
+    /// (source id, target id, base weight, weight) of every edge touching a member, in edge order
+    edges: Vec<(String, String, f32, f32)>,
+    edge_rows: Attributes,
+    removal: GraphRemoval,
+}
+
+#[wasm_bindgen]
+impl Contraction {
+    pub fn meta_id(&self) -> String {
+        self.meta_id.clone()
+    }
+
+    /// ids of the collapsed vertexes in their index order at the time of contraction
+    pub fn members(&self) -> Vec<String> {
+        self.ids.clone()
+    }
+
+    /// the index changes of removing the members, the meta vertex and its edges come after
+    pub fn removal(&self) -> GraphRemoval {
+        self.removal.clone()
+    }
+}
+
+#[wasm_bindgen]
+impl Graph {
+    /// replace the vertexes with one meta vertex placed at their centroid. the weights of the edges
+    /// between members and one outside vertex are summed into one edge, per direction for directed
+    /// graphs, edges inside the group are dropped. unknown ids are ignored, return None when no id
+    /// is known or `meta_id` belongs to a vertex outside the group.
+    /// indexes change like in `remove_vertex` for one member after the other, see
+    /// `Contraction::removal`. layouts like `NForce` can run on the result after `calc_link_bias`
+    pub fn contract(&mut self, ids: Vec<String>, meta_id: &str) -> Option<Contraction> {
+        let mut members: Vec<usize> = ids
+            .iter()
+            .filter_map(|id| self.vertexs_map.get(id).copied())
+            .collect();
+        members.sort_unstable();
+        members.dedup();
+        if members.is_empty() {
+            return None;
+        }
+        let mut is_member = vec![false; self.vertexes.len()];
+        members.iter().for_each(|v| is_member[*v] = true);
+        if let Some(index) = self.vertexs_map.get(meta_id) {
+            if !is_member[*index] {
+                return None;
+            }
+        }
+        let mut center = [0.; 3];
+        for v in &members {
+            let position = self.get_vertex_position(*v);
+            (0..3).for_each(|i| center[i] += position[i] / members.len() as f32);
+        }
+        let offsets = members
+            .iter()
+            .flat_map(|v| {
+                let position = self.get_vertex_position(*v);
+                (0..3).map(move |i| position[i] - center[i])
+            })
+            .collect();
+        let edges: Vec<usize> = (0..self.edges.len())
+            .filter(|e| is_member[self.edges[*e].source] || is_member[self.edges[*e].target])
+            .collect();
+        // (outside vertex, leaves the group) -> summed base weight and weight
+        let mut outside: BTreeMap<(usize, bool), (f32, f32)> = BTreeMap::new();
+        for e in &edges {
+            let edge = &self.edges[*e];
+            let key = match (is_member[edge.source], is_member[edge.target]) {
+                (true, false) => (edge.target, true),
+                (false, true) => (edge.source, !self.is_directed),
+                _ => continue,
+            };
+            let sum = outside.entry(key).or_insert((0., 0.));
+            sum.0 += edge.base_weight;
+            sum.1 += edge.weight;
+        }
+        let outside: Vec<(String, bool, (f32, f32))> = outside
+            .into_iter()
+            .map(|((v, leaves), weights)| (self.vertexes[v].id.clone(), leaves, weights))
+            .collect();
+        let mut contraction = Contraction {
+            meta_id: meta_id.to_string(),
+            ids: members
+                .iter()
+                .map(|v| self.vertexes[*v].id.clone())
+                .collect(),
+            offsets,
+            vertex_rows: self.vertex_attributes.select(&members),
+            edges: edges
+                .iter()
+                .map(|e| {
+                    let edge = &self.edges[*e];
+                    let (source, target) = (edge.source, edge.target);
+                    (
+                        self.vertexes[source].id.clone(),
+                        self.vertexes[target].id.clone(),
+                        edge.base_weight,
+                        edge.weight,
+                    )
+                })
+                .collect(),
+            edge_rows: self.edge_attributes.select(&edges),
+            removal: GraphRemoval::default(),
+        };
+        for id in &contraction.ids {
+            if let Some(removal) = self.remove_vertex(id) {
+                contraction.removal.append(removal);
+            }
+        }
+        self.add_vertex(meta_id, center[0], center[1], center[2]);
+        let meta = self.vertexes.len() - 1;
+        for (id, leaves, (base_weight, weight)) in outside {
+            let other = self.vertexs_map[&id];
+            if leaves {
+                self.add_edge_with_base(meta, other, base_weight, weight);
+            } else {
+                self.add_edge_with_base(other, meta, base_weight, weight);
+            }
+        }
+        Some(contraction)
+    }
+
+    /// replace the meta vertex with the members again, around where the meta vertex is now and with
+    /// their attributes. edges to vertexes removed in the meantime are skipped, restored edges get
+    /// new ids. return false when the meta vertex is gone or a member id is taken
+    pub fn expand(&mut self, contraction: &Contraction) -> bool {
+        let meta = match self.vertexs_map.get(&contraction.meta_id) {
+            Some(meta) => *meta,
+            None => return false,
+        };
+        if contraction
+            .ids
+            .iter()
+            .any(|id| *id != contraction.meta_id && self.vertexs_map.contains_key(id))
+        {
+            return false;
+        }
+        let center = self.get_vertex_position(meta);
+        self.remove_vertex(&contraction.meta_id);
+        let start = self.vertexes.len();
+        for (i, id) in contraction.ids.iter().enumerate() {
+            let offset = &contraction.offsets[i * 3..i * 3 + 3];
+            self.add_vertex(
+                id,
+                center[0] + offset[0],
+                center[1] + offset[1],
+                center[2] + offset[2],
+            );
+        }
+        self.vertex_attributes
+            .paste(start, &contraction.vertex_rows);
+        // parallel edges were parallel before the contraction too
+        let is_multigraph = self.is_multigraph;
+        self.is_multigraph = true;
+        let start = self.edges.len();
+        let mut kept = vec![];
+        for (i, (source, target, base_weight, weight)) in contraction.edges.iter().enumerate() {
+            let pair = (
+                self.vertexs_map.get(source).copied(),
+                self.vertexs_map.get(target).copied(),
+            );
+            if let (Some(source), Some(target)) = pair {
+                self.add_edge_with_base(source, target, *base_weight, *weight);
+                kept.push(i);
+            }
+        }
+        self.is_multigraph = is_multigraph;
+        self.edge_attributes
+            .paste(start, &contraction.edge_rows.select(&kept));
+        true
+    }
+
+    /// contract the vertexes of every value of a vertex attribute in the order the values first
+    /// appear, the meta vertex of value `v` gets the id "name=v". vertexes without a value stay,
+    /// groups whose meta id is taken by another vertex are skipped. expand in reverse order
+    pub fn contract_groups(&mut self, name: &str) -> Vec<Contraction> {
+        let column = match self.vertex_attributes.column(name) {
+            Some(column) => column,
+            None => return vec![],
+        };
+        let mut groups: Vec<(String, Vec<String>)> = vec![];
+        let mut group_of: HashMap<String, usize> = HashMap::new();
+        for v in 0..self.vertexes.len() {
+            if let Some(key) = column.key(v) {
+                let group = *group_of.entry(key.clone()).or_insert_with(|| {
+                    groups.push((key, vec![]));
+                    groups.len() - 1
+                });
+                groups[group].1.push(self.vertexes[v].id.clone());
+            }
+        }
+        groups
+            .into_iter()
+            .filter_map(|(key, ids)| self.contract(ids, &[name, "=", &key].concat()))
+            .collect()
+    }
+}
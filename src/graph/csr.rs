@@ -0,0 +1,211 @@
+use crate::graph::Graph;
+use std::{collections::HashMap, rc::Rc};
+use wasm_bindgen::prelude::*;
+
+/// immutable compressed sparse row copy of the adjacency. the out neighbors of v are
+/// `targets[offsets[v]..offsets[v + 1]]` sorted by index, the reverse rows hold the in neighbors
+/// the same way. undirected graphs store both directions so the reverse rows equal the forward ones.
+/// it is a snapshot, freeze a new one after the graph changes
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct Csr {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<f32>,
+    reverse_offsets: Vec<usize>,
+    sources: Vec<usize>,
+    reverse_weights: Vec<f32>,
+}
+
+impl Csr {
+    /// from an adjacency shaped like `Graph::neighbors_map`
+    pub fn from_adjacency(adjacency: &[HashMap<usize, f32>]) -> Csr {
+        let len = adjacency.len();
+        let mut offsets = Vec::with_capacity(len + 1);
+        offsets.push(0);
+        let arcs: usize = adjacency.iter().map(|map| map.len()).sum();
+        let mut targets = Vec::with_capacity(arcs);
+        let mut weights = Vec::with_capacity(arcs);
+        let mut row: Vec<(usize, f32)> = vec![];
+        let mut in_degree = vec![0; len];
+        for map in adjacency {
+            row.clear();
+            row.extend(map.iter().map(|(k, w)| (*k, *w)));
+            row.sort_unstable_by_key(|(k, _)| *k);
+            for (target, weight) in &row {
+                targets.push(*target);
+                weights.push(*weight);
+                in_degree[*target] += 1;
+            }
+            offsets.push(targets.len());
+        }
+        // counting sort by target, walking the sources in order keeps every reverse row sorted
+        let mut reverse_offsets = Vec::with_capacity(len + 1);
+        reverse_offsets.push(0);
+        for d in &in_degree {
+            reverse_offsets.push(reverse_offsets.last().unwrap() + d);
+        }
+        let mut cursor = reverse_offsets.clone();
+        let mut sources = vec![0; arcs];
+        let mut reverse_weights = vec![0.; arcs];
+        for source in 0..len {
+            for i in offsets[source]..offsets[source + 1] {
+                let slot = &mut cursor[targets[i]];
+                sources[*slot] = source;
+                reverse_weights[*slot] = weights[i];
+                *slot += 1;
+            }
+        }
+        Csr {
+            offsets,
+            targets,
+            weights,
+            reverse_offsets,
+            sources,
+            reverse_weights,
+        }
+    }
+
+    /// out and in rows merged without self loops, for algorithms where direction doesn't matter.
+    /// a pair linked both ways keeps the out weight
+    pub fn undirected(&self) -> Csr {
+        let mut offsets = Vec::with_capacity(self.offsets.len());
+        offsets.push(0);
+        let mut targets = Vec::with_capacity(self.arcs_len());
+        let mut weights = Vec::with_capacity(self.arcs_len());
+        for v in 0..self.len() {
+            let (out, out_w) = (self.out_neighbors(v), self.out_weights(v));
+            let (inn, in_w) = (self.in_neighbors(v), self.in_weights(v));
+            let (mut i, mut j) = (0, 0);
+            while i < out.len() || j < inn.len() {
+                let (u, w) = if j == inn.len() || (i < out.len() && out[i] <= inn[j]) {
+                    if j < inn.len() && out[i] == inn[j] {
+                        j += 1;
+                    }
+                    i += 1;
+                    (out[i - 1], out_w[i - 1])
+                } else {
+                    j += 1;
+                    (inn[j - 1], in_w[j - 1])
+                };
+                if u != v {
+                    targets.push(u);
+                    weights.push(w);
+                }
+            }
+            offsets.push(targets.len());
+        }
+        Csr {
+            reverse_offsets: offsets.clone(),
+            sources: targets.clone(),
+            reverse_weights: weights.clone(),
+            offsets,
+            targets,
+            weights,
+        }
+    }
+
+    pub fn out_neighbors(&self, v: usize) -> &[usize] {
+        &self.targets[self.offsets[v]..self.offsets[v + 1]]
+    }
+
+    pub fn out_weights(&self, v: usize) -> &[f32] {
+        &self.weights[self.offsets[v]..self.offsets[v + 1]]
+    }
+
+    /// (target, weight) pairs
+    pub fn out_edges(&self, v: usize) -> impl Iterator<Item = (usize, f32)> + '_ {
+        self.out_neighbors(v)
+            .iter()
+            .copied()
+            .zip(self.out_weights(v).iter().copied())
+    }
+
+    pub fn in_neighbors(&self, v: usize) -> &[usize] {
+        &self.sources[self.reverse_offsets[v]..self.reverse_offsets[v + 1]]
+    }
+
+    pub fn in_weights(&self, v: usize) -> &[f32] {
+        &self.reverse_weights[self.reverse_offsets[v]..self.reverse_offsets[v + 1]]
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// stored arcs, twice the edges for undirected graphs
+    pub fn arcs_len(&self) -> usize {
+        self.targets.len()
+    }
+}
+
+fn to_u32(values: &[usize]) -> Vec<u32> {
+    values.iter().map(|v| *v as u32).collect()
+}
+
+#[wasm_bindgen]
+impl Csr {
+    #[wasm_bindgen(constructor)]
+    pub fn new(graph: &Graph) -> Csr {
+        Csr::from_adjacency(&graph.neighbors_map)
+    }
+
+    pub fn offsets(&self) -> Vec<u32> {
+        to_u32(&self.offsets)
+    }
+
+    pub fn targets(&self) -> Vec<u32> {
+        to_u32(&self.targets)
+    }
+
+    pub fn weights(&self) -> Vec<f32> {
+        self.weights.clone()
+    }
+
+    pub fn reverse_offsets(&self) -> Vec<u32> {
+        to_u32(&self.reverse_offsets)
+    }
+
+    pub fn sources(&self) -> Vec<u32> {
+        to_u32(&self.sources)
+    }
+
+    pub fn reverse_weights(&self) -> Vec<f32> {
+        self.reverse_weights.clone()
+    }
+
+    pub fn out_degree(&self, v: usize) -> usize {
+        self.offsets[v + 1] - self.offsets[v]
+    }
+
+    pub fn in_degree(&self, v: usize) -> usize {
+        self.reverse_offsets[v + 1] - self.reverse_offsets[v]
+    }
+}
+
+impl Graph {
+    /// the csr of the current adjacency, built once per revision and shared by the algorithms
+    pub fn snapshot(&self) -> Rc<Csr> {
+        let mut cache = self.csr_cache.borrow_mut();
+        if let Some((revision, csr)) = cache.as_ref() {
+            if *revision == self.revision {
+                return csr.clone();
+            }
+        }
+        let csr = Rc::new(Csr::new(self));
+        *cache = Some((self.revision, csr.clone()));
+        csr
+    }
+}
+
+#[wasm_bindgen]
+impl Graph {
+    /// a copy of `snapshot` that js owns
+    pub fn freeze(&self) -> Csr {
+        Csr::clone(&self.snapshot())
+    }
+}
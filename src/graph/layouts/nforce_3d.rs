@@ -34,7 +34,7 @@ impl NForce {
         }
     }
 
-    /// will calc graph degree and then calc link bias
+    /// will calc graph degree and then calc link bias, one per edge for `get_bias`
     pub fn calc_link_bias(&mut self, graph: &mut Graph) -> Vec<f64> {
         graph.calc_degree();
         let vertexes = &graph.vertexes;
@@ -123,32 +123,39 @@ impl NForce {
         vertex.vz += center_scale * z_dir;
     }
 
+    /// every linked pair pulls once, parallel edges share the pull
     fn apply_link_force(&self, graph: &mut Graph) {
-        let links = &graph.edges;
-        for (i, link) in links.iter().enumerate() {
-            if link.source == link.target {
-                continue;
-            }
-            let source = &graph.vertexes[link.source];
-            let target = &graph.vertexes[link.target];
-            let dx = source.x + source.vx - target.x - target.vx;
-            let dy = source.y + source.vy - target.y - target.vy;
-            let dz = source.z + source.vz - target.z - target.vz;
-            let l = f64::sqrt(dx * dx + dy * dy + dz * dz);
-            let scale = (l - self.link_distance) / l * self.link_strength * self.alpha;
-            let bias = (1.0 - self.bias[i]) * scale;
-            {
-                let source = &mut graph.vertexes[link.source];
-                source.vx -= dx * bias;
-                source.vy -= dy * bias;
-                source.vz -= dz * bias;
-            }
-            {
-                let bias2 = self.bias[i] * scale;
-                let target = &mut graph.vertexes[link.target];
-                target.vx += dx * bias2;
-                target.vy += dy * bias2;
-                target.vz += dz * bias2;
+        let csr = graph.snapshot();
+        for source in 0..csr.len() {
+            for target in csr.out_neighbors(source) {
+                let target = *target;
+                if target == source || (!graph.is_directed && target < source) {
+                    continue;
+                }
+                let (source, target) = (&graph.vertexes[source], &graph.vertexes[target]);
+                let dx = source.x + source.vx - target.x - target.vx;
+                let dy = source.y + source.vy - target.y - target.vy;
+                let dz = source.z + source.vz - target.z - target.vz;
+                let l = f64::sqrt(dx * dx + dy * dy + dz * dz);
+                let scale = (l - self.link_distance) / l * self.link_strength * self.alpha;
+                // the same bias as `calc_link_bias`
+                let link_bias =
+                    source.degree as f64 / (source.degree as f64 + target.degree as f64);
+                let (source, target) = (source.index, target.index);
+                {
+                    let bias = (1.0 - link_bias) * scale;
+                    let source = &mut graph.vertexes[source];
+                    source.vx -= dx * bias;
+                    source.vy -= dy * bias;
+                    source.vz -= dz * bias;
+                }
+                {
+                    let bias2 = link_bias * scale;
+                    let target = &mut graph.vertexes[target];
+                    target.vx += dx * bias2;
+                    target.vy += dy * bias2;
+                    target.vz += dz * bias2;
+                }
             }
         }
     }
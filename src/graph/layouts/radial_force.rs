@@ -1,6 +1,7 @@
 use crate::{
     graph::{
         algos::{apsp, cnc},
+        csr::Csr,
         Graph,
     },
     log,
@@ -25,9 +26,10 @@ impl RadialForce {
         if line_length == 0. {
             line_length = 200.;
         }
+        let csr = graph.snapshot();
         let node_index;
         if node_id == "" {
-            let sort_nodes = Self::sort_nodes_by_degree(&graph);
+            let sort_nodes = Self::sort_nodes_by_degree(&csr);
             node_index = sort_nodes[0];
         } else {
             if !graph.vertexs_map.contains_key(node_id) {
@@ -39,7 +41,7 @@ impl RadialForce {
         // set 0,0 to default position
         graph.set_vertex_position(node_index as usize, vec![0., 0., 0.]);
         // calculate the position of  node's neighbors
-        let mut d = apsp::make_floyd_warshall(graph);
+        let mut d = apsp::make_csr_floyd_warshall(&csr);
         let max_distance = Self::get_max_distance(&d);
         // get cnc value
         let c = cnc::make_cnc(&d);
@@ -168,11 +170,11 @@ impl RadialForce {
         max_distance
     }
 
-    fn sort_nodes_by_degree(graph: &Graph) -> Vec<usize> {
-        let mut nodes: Vec<usize> = (0..graph.vertexes.len()).collect();
+    fn sort_nodes_by_degree(csr: &Csr) -> Vec<usize> {
+        let mut nodes: Vec<usize> = (0..csr.len()).collect();
         nodes.sort_by(|a, b| {
-            let a_degree = &graph.vertexes[*a].degree;
-            let b_degree = &graph.vertexes[*b].degree;
+            let a_degree = csr.out_degree(*a) + csr.in_degree(*a);
+            let b_degree = csr.out_degree(*b) + csr.in_degree(*b);
             a_degree.cmp(&b_degree)
         });
         nodes
@@ -1,10 +1,18 @@
 pub mod algos;
+pub mod attributes;
+pub mod contraction;
+pub mod csr;
 pub mod layouts;
+pub mod multigraph;
+pub mod subgraph;
 
 #[cfg(test)]
 mod tests;
 pub mod utils;
-use std::{collections::HashMap, fmt::Display};
+use attributes::Attributes;
+use csr::Csr;
+use multigraph::CollapsePolicy;
+use std::{cell::RefCell, collections::HashMap, fmt::Display, rc::Rc};
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
@@ -23,7 +31,10 @@ pub struct Vertex {
     vx: f64,
     vy: f64,
     vz: f64,
+    /// in + out degree, a self loop counts twice
     pub degree: usize,
+    in_degree: usize,
+    out_degree: usize,
 }
 impl Display for Vertex {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -35,28 +46,86 @@ impl Display for Vertex {
     }
 }
 pub struct Edge {
+    id: usize,
     source: usize,
     target: usize,
     pub weight: f32,
+    /// the weight given when the edge was added, `use_edge_weight` only changes `weight`
+    base_weight: f32,
 }
 impl Display for Edge {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "(source: {}, target: {}, weight: {})",
-            self.source, self.target, self.weight
+            "(id: {}, source: {}, target: {}, weight: {})",
+            self.id, self.source, self.target, self.weight
         )
     }
 }
 
+/// index changes made by a removal. vertexes and edges are swap-removed: replay the
+/// `[from, to, ...]` pairs in order by copying slot `from` into slot `to`, then truncate the
+/// buffer to the new length
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct GraphRemoval {
+    vertex_moves: Vec<u32>,
+    edge_moves: Vec<u32>,
+    removed_edges: Vec<u32>,
+}
+
+impl GraphRemoval {
+    /// the moves of a later removal replay after these ones
+    pub fn append(&mut self, later: GraphRemoval) {
+        self.vertex_moves.extend(later.vertex_moves);
+        self.edge_moves.extend(later.edge_moves);
+        self.removed_edges.extend(later.removed_edges);
+    }
+}
+
+#[wasm_bindgen]
+impl GraphRemoval {
+    pub fn vertex_moves(&self) -> Vec<u32> {
+        self.vertex_moves.clone()
+    }
+
+    pub fn edge_moves(&self) -> Vec<u32> {
+        self.edge_moves.clone()
+    }
+
+    /// indexes the removed edges had when they were removed, in removal order
+    pub fn removed_edges(&self) -> Vec<u32> {
+        self.removed_edges.clone()
+    }
+}
+
 #[wasm_bindgen]
 pub struct Graph {
     vertexes: Vec<Vertex>,
     edges: Vec<Edge>,
     vertexs_map: HashMap<String, usize>, // convert vertex id to index
     neighbors_map: Vec<HashMap<usize, f32>>,
+    in_neighbors_map: Vec<HashMap<usize, f32>>, // directed graphs only, see `in_neighbor_weights`
     is_directed: bool,
     vertexs_position: Vec<f32>,
+    vertex_attributes: Attributes,
+    edge_attributes: Attributes,
+    is_multigraph: bool,
+    collapse_policy: CollapsePolicy,
+    edges_map: Vec<usize>, // convert edge id to index, usize::MAX once the edge is removed
+    incident_edges: Vec<Vec<usize>>, // ids of the edges touching every vertex in adding order
+    parallel_edges: Vec<HashMap<usize, Vec<usize>>>, // edge ids of the pairs linked more than once
+    next_edge_id: usize,
+    // flat copies handed to js by pointer, the getters refill them when the graph changed since
+    revision: u64,
+    /// revision of the edge, weight and degree buffers
+    buffer_revisions: [u64; 3],
+    /// the csr `snapshot` built at a revision
+    csr_cache: RefCell<Option<(u64, Rc<Csr>)>>,
+    edge_buffer: Vec<u32>,
+    weight_buffer: Vec<f32>,
+    degree_buffer: Vec<u32>,
+    last_result: Vec<f32>,
 }
 
 #[wasm_bindgen]
@@ -68,13 +137,40 @@ impl Graph {
             vertexes: vec![],
             edges: Vec::new(),
             neighbors_map: vec![],
+            in_neighbors_map: vec![],
             vertexs_map: HashMap::new(),
             vertexs_position: vec![],
+            vertex_attributes: Attributes::default(),
+            edge_attributes: Attributes::default(),
+            is_multigraph: false,
+            collapse_policy: CollapsePolicy::KeepLast,
+            edges_map: vec![],
+            incident_edges: vec![],
+            parallel_edges: vec![],
+            next_edge_id: 0,
+            revision: 1,
+            buffer_revisions: [0; 3],
+            csr_cache: RefCell::new(None),
+            edge_buffer: vec![],
+            weight_buffer: vec![],
+            degree_buffer: vec![],
+            last_result: vec![],
         }
     }
 
+    /// switching rebuilds the adjacency from the edges, edges added in both directions become
+    /// parallel edges of an undirected graph and are collapsed by the collapse policy
     pub fn set_directed(&mut self, is_directed: bool) {
+        if self.is_directed == is_directed {
+            return;
+        }
         self.is_directed = is_directed;
+        self.rebuild_adjacency();
+        self.touch();
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.is_directed
     }
 
     pub fn add_vertex(&mut self, id: &str, x: f32, y: f32, mut z: f32) {
@@ -93,33 +189,228 @@ impl Graph {
             vy: 0.,
             vz: 0.,
             degree: 0,
+            in_degree: 0,
+            out_degree: 0,
         };
         self.vertexes.push(vertex);
         self.vertexs_map.insert(key, index);
         self.neighbors_map.push(HashMap::new());
+        if self.is_directed {
+            self.in_neighbors_map.push(HashMap::new());
+        }
+        self.incident_edges.push(vec![]);
+        self.parallel_edges.push(HashMap::new());
         self.vertexs_position.push(x);
         self.vertexs_position.push(y);
         self.vertexs_position.push(z);
+        self.vertex_attributes.push();
+        self.touch();
+    }
+
+    /// return the edge id, see `set_multigraph` for edges between vertexes that are already linked
+    pub fn add_edge(&mut self, source: &str, target: &str, weight: f32) -> usize {
+        let source_index = self.vertexs_map[source];
+        let target_index = self.vertexs_map[target];
+        self.add_edge_by_index(source_index, target_index, weight)
     }
 
-    pub fn add_edge(&mut self, source: &str, target: &str, mut weight: f32) {
+    /// `add_edge` with vertex indexes instead of ids
+    pub fn add_edge_by_index(
+        &mut self,
+        source_index: usize,
+        target_index: usize,
+        mut weight: f32,
+    ) -> usize {
         if weight.is_infinite() || weight.is_nan() || weight == 0.0 {
             weight = 1.;
         }
-        // not clone but use reference maybe good
-        //undirected graph
-        let source_index = self.vertexs_map[source];
-        let target_index = self.vertexs_map[target];
-        self.neighbors_map[source_index].insert(target_index, weight);
-        if !self.is_directed {
-            self.neighbors_map[target_index].insert(source_index, weight);
+        if !self.is_multigraph && self.neighbors_map[source_index].contains_key(&target_index) {
+            let existing = self.pair_edge_ids(source_index, target_index);
+            if let Some(id) = existing.first().copied() {
+                let index = self.edges_map[id];
+                self.edges[index].weight = weight;
+                self.edges[index].base_weight = weight;
+                self.refresh_weight(source_index, target_index);
+                self.touch();
+                return id;
+            }
         }
+        let id = self.next_edge_id;
+        self.next_edge_id += 1;
+        self.edges_map.resize(id, usize::MAX);
+        self.edges_map.push(self.edges.len());
         let edge = Edge {
+            id,
             source: source_index,
             target: target_index,
             weight,
+            base_weight: weight,
         };
         self.edges.push(edge);
+        self.edge_attributes.push();
+        self.count_degree(source_index, target_index, true);
+        self.link_edge(id, source_index, target_index);
+        self.touch();
+        id
+    }
+
+    /// bulk load: a graph with one vertex per xyz triple of `positions` and one edge per
+    /// `sources[i] -> targets[i]` pair of vertex indexes. `weights` defaults to 1 and `ids` to the
+    /// vertex index as a string. undefined when `sources` and `targets` differ in length
+    pub fn from_arrays(
+        positions: &[f32],
+        sources: &[u32],
+        targets: &[u32],
+        weights: Option<Vec<f32>>,
+        ids: Option<Vec<String>>,
+        is_directed: bool,
+    ) -> Option<Graph> {
+        if sources.len() != targets.len() {
+            return None;
+        }
+        let mut graph = Graph::new();
+        graph.set_directed(is_directed);
+        graph.add_vertexes(positions, ids);
+        graph.add_edges(sources, targets, weights);
+        Some(graph)
+    }
+
+    /// add one vertex per xyz triple, `ids` defaults to the vertex index as a string, also for
+    /// the vertexes past the end of a short `ids`. a default id that is taken becomes the next
+    /// free number
+    pub fn add_vertexes(&mut self, positions: &[f32], ids: Option<Vec<String>>) {
+        let count = positions.len() / 3;
+        self.vertexes.reserve(count);
+        self.vertexs_position.reserve(count * 3);
+        let mut next_id = 0;
+        for (i, position) in positions.chunks_exact(3).enumerate() {
+            let id = match ids.as_ref().and_then(|ids| ids.get(i)) {
+                Some(id) => id.clone(),
+                None => {
+                    next_id = next_id.max(self.vertexes.len());
+                    while self.vertexs_map.contains_key(&next_id.to_string()) {
+                        next_id += 1;
+                    }
+                    next_id.to_string()
+                }
+            };
+            self.add_vertex(&id, position[0], position[1], position[2]);
+        }
+    }
+
+    /// add one edge per `sources[i] -> targets[i]` pair of vertex indexes, pairs that point
+    /// outside the vertexes are skipped. `weights` defaults to 1. return false and add nothing
+    /// when `sources` and `targets` differ in length
+    pub fn add_edges(
+        &mut self,
+        sources: &[u32],
+        targets: &[u32],
+        weights: Option<Vec<f32>>,
+    ) -> bool {
+        if sources.len() != targets.len() {
+            return false;
+        }
+        let len = self.vertexes.len();
+        self.edges.reserve(sources.len());
+        for (i, (source, target)) in sources.iter().zip(targets).enumerate() {
+            let (source, target) = (*source as usize, *target as usize);
+            if source >= len || target >= len {
+                continue;
+            }
+            let weight = weights
+                .as_ref()
+                .and_then(|weights| weights.get(i).copied())
+                .unwrap_or(1.);
+            self.add_edge_by_index(source, target, weight);
+        }
+        true
+    }
+
+    /// remove the vertex and every edge touching it, the last vertex takes its index
+    pub fn remove_vertex(&mut self, id: &str) -> Option<GraphRemoval> {
+        let index = *self.vertexs_map.get(id)?;
+        // the list goes away with the vertex, unlinking its edges only touches the other ends
+        let mut incident: Vec<usize> = std::mem::take(&mut self.incident_edges[index])
+            .iter()
+            .map(|id| self.edges_map[*id])
+            .collect();
+        incident.sort_unstable();
+        let mut removal = GraphRemoval::default();
+        self.swap_remove_edges(incident, &mut removal);
+        self.vertexs_map.remove(id);
+        let last = self.vertexes.len() - 1;
+        self.vertexes.swap_remove(index);
+        self.vertex_attributes.swap_remove(index);
+        self.neighbors_map.swap_remove(index);
+        if self.is_directed {
+            self.in_neighbors_map.swap_remove(index);
+        }
+        self.incident_edges.swap_remove(index);
+        self.parallel_edges.swap_remove(index);
+        if index != last {
+            removal.vertex_moves = vec![last as u32, index as u32];
+            self.vertexes[index].index = index;
+            self.vertexs_map
+                .insert(self.vertexes[index].id.clone(), index);
+            self.vertexs_position
+                .copy_within(last * 3..last * 3 + 3, index * 3);
+            // only the neighbors of the moved vertex name it
+            let mut others: Vec<usize> = self.neighbors_map[index]
+                .keys()
+                .chain(self.in_neighbor_weights(index).keys())
+                .map(|w| if *w == last { index } else { *w })
+                .collect();
+            others.sort_unstable();
+            others.dedup();
+            let rekey = |neighbors: &mut HashMap<usize, f32>| {
+                if let Some(weight) = neighbors.remove(&last) {
+                    neighbors.insert(index, weight);
+                }
+            };
+            for w in others {
+                rekey(&mut self.neighbors_map[w]);
+                if self.is_directed {
+                    rekey(&mut self.in_neighbors_map[w]);
+                }
+                if let Some(ids) = self.parallel_edges[w].remove(&last) {
+                    self.parallel_edges[w].insert(index, ids);
+                }
+            }
+            let moved: Vec<usize> = self.incident_edges[index]
+                .iter()
+                .map(|id| self.edges_map[*id])
+                .collect();
+            for e in moved {
+                let edge = &mut self.edges[e];
+                if edge.source == last {
+                    edge.source = index;
+                }
+                if edge.target == last {
+                    edge.target = index;
+                }
+            }
+        }
+        self.vertexs_position.truncate(last * 3);
+        self.touch();
+        Some(removal)
+    }
+
+    /// remove every edge from source to target, both directions for undirected graphs
+    pub fn remove_edge(&mut self, source: &str, target: &str) -> Option<GraphRemoval> {
+        let source = *self.vertexs_map.get(source)?;
+        let target = *self.vertexs_map.get(target)?;
+        let mut matched: Vec<usize> = self
+            .pair_edge_ids(source, target)
+            .iter()
+            .map(|id| self.edges_map[*id])
+            .collect();
+        if matched.is_empty() {
+            return None;
+        }
+        matched.sort_unstable();
+        let mut removal = GraphRemoval::default();
+        self.swap_remove_edges(matched, &mut removal);
+        Some(removal)
     }
 
     pub fn get_vertexes_len(&self) -> usize {
@@ -137,14 +428,42 @@ impl Graph {
         }
     }
 
+    /// degrees are kept current by every change, this only recounts them from the edges
     pub fn calc_degree(&mut self) {
-        for edge in &self.edges {
-            self.vertexes[edge.source].degree += 1;
-            if edge.source == edge.target {
-                continue;
-            }
-            self.vertexes[edge.target].degree += 1;
+        for vertex in self.vertexes.iter_mut() {
+            vertex.in_degree = 0;
+            vertex.out_degree = 0;
+            vertex.degree = 0;
+        }
+        for i in 0..self.edges.len() {
+            self.count_degree(self.edges[i].source, self.edges[i].target, true);
         }
+        self.touch();
+    }
+
+    /// edges leaving the vertex, all incident edges for undirected graphs
+    pub fn out_degree(&self, index: usize) -> usize {
+        let vertex = &self.vertexes[index];
+        if self.is_directed {
+            vertex.out_degree
+        } else {
+            vertex.degree
+        }
+    }
+
+    /// edges entering the vertex, all incident edges for undirected graphs
+    pub fn in_degree(&self, index: usize) -> usize {
+        let vertex = &self.vertexes[index];
+        if self.is_directed {
+            vertex.in_degree
+        } else {
+            vertex.degree
+        }
+    }
+
+    /// in + out degree, a self loop counts twice
+    pub fn degree(&self, index: usize) -> usize {
+        self.vertexes[index].degree
     }
 
     pub fn set_vertex_position(&mut self, index: usize, position: Vec<f32>) {
@@ -161,6 +480,10 @@ impl Graph {
         ]
     }
 
+    pub fn get_in_neighbors(&self, index: usize) -> Vec<usize> {
+        self.in_neighbor_weights(index).keys().copied().collect()
+    }
+
     pub fn get_neighbors(&self, index: usize) -> Vec<usize> {
         let mut neighbors = vec![];
         for (neighbor, _) in &self.neighbors_map[index] {
@@ -174,7 +497,14 @@ impl Graph {
         self.edges.clear();
         self.vertexs_map.clear();
         self.neighbors_map.clear();
+        self.in_neighbors_map.clear();
         self.vertexs_position.clear();
+        self.vertex_attributes.clear();
+        self.edge_attributes.clear();
+        self.edges_map.clear();
+        self.incident_edges.clear();
+        self.parallel_edges.clear();
+        self.touch();
     }
 
     pub fn get_nodes_position_ptr(&mut self) -> *const f32 {
@@ -185,13 +515,160 @@ impl Graph {
         self.vertexs_position.clone()
     }
 
+    /// [source, target, ...] vertex indexes of every edge, 2 * edges len items.
+    /// like every buffer pointer the view is only refilled after the graph changed, it is invalid
+    /// once the graph changes or the wasm memory grows, call the getter again then
+    pub fn get_edges_ptr(&mut self) -> *const u32 {
+        if self.buffer_revisions[0] != self.revision {
+            self.buffer_revisions[0] = self.revision;
+            self.edge_buffer.clear();
+            self.edge_buffer.extend(
+                self.edges
+                    .iter()
+                    .flat_map(|edge| [edge.source as u32, edge.target as u32]),
+            );
+        }
+        self.edge_buffer.as_ptr()
+    }
+
+    /// edges len items
+    pub fn get_edge_weights_ptr(&mut self) -> *const f32 {
+        if self.buffer_revisions[1] != self.revision {
+            self.buffer_revisions[1] = self.revision;
+            self.weight_buffer.clear();
+            self.weight_buffer
+                .extend(self.edges.iter().map(|edge| edge.weight));
+        }
+        self.weight_buffer.as_ptr()
+    }
+
+    /// vertexes len items, in + out degree
+    pub fn get_degrees_ptr(&mut self) -> *const u32 {
+        if self.buffer_revisions[2] != self.revision {
+            self.buffer_revisions[2] = self.revision;
+            self.degree_buffer.clear();
+            self.degree_buffer
+                .extend(self.vertexes.iter().map(|vertex| vertex.degree as u32));
+        }
+        self.degree_buffer.as_ptr()
+    }
+
+    /// result of the last `run_algo` call, `get_last_result_len` items. only `run_algo` stores
+    /// its result here, the other algorithm functions return their own arrays
+    pub fn get_last_result_ptr(&self) -> *const f32 {
+        self.last_result.as_ptr()
+    }
+
+    pub fn get_last_result_len(&self) -> usize {
+        self.last_result.len()
+    }
+
     /// return the pointer of the position of the nodes
     pub fn run_algo(&mut self, name: &str, config: &str) -> Vec<f32> {
-        match name {
+        let result = match name {
             "apsp" => algos::apsp::run(self),
             "cnc" => algos::cnc::run(self),
             "bfs" => algos::bfs::run(self, config),
+            "sssp" => algos::sssp::run(self, config),
+            "scc" => algos::scc::run(self),
             _ => vec![0.],
+        };
+        self.last_result.clone_from(&result);
+        result
+    }
+}
+
+impl Graph {
+    fn count_degree(&mut self, source: usize, target: usize, add: bool) {
+        let change = |value: &mut usize| {
+            if add {
+                *value += 1
+            } else {
+                *value -= 1
+            }
+        };
+        change(&mut self.vertexes[source].out_degree);
+        change(&mut self.vertexes[target].in_degree);
+        for v in [source, target] {
+            let vertex = &mut self.vertexes[v];
+            vertex.degree = vertex.in_degree + vertex.out_degree;
+        }
+    }
+
+    /// out neighbors with their collapsed weights, the adjacency `freeze` copies into the csr
+    pub fn neighbor_weights(&self, index: usize) -> &HashMap<usize, f32> {
+        &self.neighbors_map[index]
+    }
+
+    /// in neighbors with their collapsed weights, the out neighbors for undirected graphs
+    pub fn in_neighbor_weights(&self, index: usize) -> &HashMap<usize, f32> {
+        if self.is_directed {
+            &self.in_neighbors_map[index]
+        } else {
+            &self.neighbors_map[index]
+        }
+    }
+
+    /// add an edge that keeps the weight it was added with apart from its current weight, for
+    /// graphs built out of other graphs
+    pub(crate) fn add_edge_with_base(
+        &mut self,
+        source: usize,
+        target: usize,
+        base_weight: f32,
+        weight: f32,
+    ) -> usize {
+        let id = self.add_edge_by_index(source, target, base_weight);
+        let index = self.edges_map[id];
+        if self.edges[index].weight != weight {
+            self.edges[index].weight = weight;
+            self.refresh_weight(source, target);
+        }
+        id
+    }
+
+    /// the buffers behind the pointer getters and the csr snapshot are stale after this.
+    /// writes through the pub `weight` and `degree` fields bypass it
+    pub(crate) fn touch(&mut self) {
+        self.revision += 1;
+    }
+
+    /// relink every edge after the directedness changed
+    fn rebuild_adjacency(&mut self) {
+        let len = self.vertexes.len();
+        self.in_neighbors_map = vec![HashMap::new(); if self.is_directed { len } else { 0 }];
+        for v in 0..len {
+            self.neighbors_map[v].clear();
+            self.incident_edges[v].clear();
+            self.parallel_edges[v].clear();
+        }
+        // removals swap edges around, ids keep the adding order the parallel edge lists need
+        let mut order: Vec<usize> = (0..self.edges.len()).collect();
+        order.sort_unstable_by_key(|i| self.edges[*i].id);
+        for i in order {
+            let (id, source, target) =
+                (self.edges[i].id, self.edges[i].source, self.edges[i].target);
+            self.link_edge(id, source, target);
+        }
+    }
+
+    /// `indexes` ascending, removing from the back keeps every pending index valid
+    fn swap_remove_edges(&mut self, indexes: Vec<usize>, removal: &mut GraphRemoval) {
+        for index in indexes.into_iter().rev() {
+            let edge = self.edges.swap_remove(index);
+            self.edge_attributes.swap_remove(index);
+            self.edges_map[edge.id] = usize::MAX;
+            if index < self.edges.len() {
+                self.edges_map[self.edges[index].id] = index;
+            }
+            self.unlink_edge(edge.id, edge.source, edge.target);
+            self.count_degree(edge.source, edge.target, false);
+            self.touch();
+            removal.removed_edges.push(index as u32);
+            if index < self.edges.len() {
+                removal.edge_moves.push(self.edges.len() as u32);
+                removal.edge_moves.push(index as u32);
+            }
         }
     }
 }
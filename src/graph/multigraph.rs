@@ -0,0 +1,230 @@
+use crate::graph::{Graph, GraphRemoval};
+use wasm_bindgen::prelude::*;
+
+/// how the weights of parallel edges become the single weight in `neighbors_map`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CollapsePolicy {
+    KeepFirst,
+    KeepLast,
+    Sum,
+    Min,
+    Max,
+}
+
+impl CollapsePolicy {
+    pub fn from_name(name: &str) -> Option<CollapsePolicy> {
+        match name {
+            "keep-first" | "first" => Some(CollapsePolicy::KeepFirst),
+            "keep-last" | "last" | "" => Some(CollapsePolicy::KeepLast),
+            "sum" => Some(CollapsePolicy::Sum),
+            "min" => Some(CollapsePolicy::Min),
+            "max" => Some(CollapsePolicy::Max),
+            _ => None,
+        }
+    }
+
+    /// `weights` in adding order, never empty
+    pub fn collapse(&self, weights: impl Iterator<Item = f32>) -> f32 {
+        let mut weights = weights;
+        let first = weights.next().unwrap();
+        match self {
+            CollapsePolicy::KeepFirst => first,
+            CollapsePolicy::KeepLast => weights.last().unwrap_or(first),
+            CollapsePolicy::Sum => weights.fold(first, |a, b| a + b),
+            CollapsePolicy::Min => weights.fold(first, f32::min),
+            CollapsePolicy::Max => weights.fold(first, f32::max),
+        }
+    }
+}
+
+impl Graph {
+    /// the edge of a pair linked once, found in the shorter incidence list of the two ends
+    fn find_pair_edge(&self, source: usize, target: usize) -> Option<usize> {
+        if !self.neighbors_map[source].contains_key(&target) {
+            return None;
+        }
+        let row = if self.incident_edges[source].len() <= self.incident_edges[target].len() {
+            &self.incident_edges[source]
+        } else {
+            &self.incident_edges[target]
+        };
+        row.iter().copied().find(|id| {
+            let edge = &self.edges[self.edges_map[*id]];
+            (edge.source == source && edge.target == target)
+                || (!self.is_directed && edge.source == target && edge.target == source)
+        })
+    }
+
+    /// ids of the edges from source to target in adding order, both directions for undirected
+    /// graphs
+    pub(crate) fn pair_edge_ids(&self, source: usize, target: usize) -> Vec<usize> {
+        match self.parallel_edges[source].get(&target) {
+            Some(ids) => ids.clone(),
+            None => self.find_pair_edge(source, target).into_iter().collect(),
+        }
+    }
+
+    /// put an edge already pushed to `edges` into the adjacency. a pair keeps an id list only
+    /// once a second edge links it
+    pub(crate) fn link_edge(&mut self, id: usize, source: usize, target: usize) {
+        let is_directed = self.is_directed;
+        let is_linked = self.neighbors_map[source].contains_key(&target);
+        if is_linked {
+            if let Some(ids) = self.parallel_edges[source].get_mut(&target) {
+                ids.push(id);
+                if !is_directed && source != target {
+                    self.parallel_edges[target]
+                        .get_mut(&source)
+                        .unwrap()
+                        .push(id);
+                }
+            } else if let Some(first) = self.find_pair_edge(source, target) {
+                self.parallel_edges[source].insert(target, vec![first, id]);
+                if !is_directed && source != target {
+                    self.parallel_edges[target].insert(source, vec![first, id]);
+                }
+            }
+        }
+        self.incident_edges[source].push(id);
+        if source != target {
+            self.incident_edges[target].push(id);
+        }
+        if is_linked {
+            self.refresh_weight(source, target);
+        } else {
+            let weight = self.edges[self.edges_map[id]].weight;
+            self.set_pair_weight(source, target, Some(weight));
+        }
+    }
+
+    pub(crate) fn unlink_edge(&mut self, id: usize, source: usize, target: usize) {
+        for v in [source, target] {
+            let ids = &mut self.incident_edges[v];
+            if let Some(position) = ids.iter().position(|other| *other == id) {
+                ids.remove(position);
+            }
+        }
+        let is_directed = self.is_directed;
+        let mut unlink = |from: usize, to: usize| {
+            if let Some(ids) = self.parallel_edges[from].get_mut(&to) {
+                ids.retain(|other| *other != id);
+                // a single edge is found through the incidence lists again
+                if ids.len() == 1 {
+                    self.parallel_edges[from].remove(&to);
+                }
+            }
+        };
+        unlink(source, target);
+        if !is_directed {
+            unlink(target, source);
+        }
+        self.refresh_weight(source, target);
+    }
+
+    /// recompute the `neighbors_map` weight of a vertex pair from its edges
+    pub(crate) fn refresh_weight(&mut self, source: usize, target: usize) {
+        let weight = match self.parallel_edges[source].get(&target) {
+            Some(ids) => Some(
+                self.collapse_policy
+                    .collapse(ids.iter().map(|id| self.edges[self.edges_map[*id]].weight)),
+            ),
+            None => self
+                .find_pair_edge(source, target)
+                .map(|id| self.edges[self.edges_map[id]].weight),
+        };
+        self.set_pair_weight(source, target, weight);
+    }
+
+    /// undirected graphs store the pair both ways in `neighbors_map` instead of in the in map
+    fn set_pair_weight(&mut self, source: usize, target: usize, weight: Option<f32>) {
+        let reverse = if self.is_directed {
+            &mut self.in_neighbors_map[target]
+        } else {
+            &mut self.neighbors_map[target]
+        };
+        match weight {
+            Some(weight) => {
+                reverse.insert(source, weight);
+                self.neighbors_map[source].insert(target, weight);
+            }
+            None => {
+                reverse.remove(&source);
+                self.neighbors_map[source].remove(&target);
+            }
+        }
+    }
+
+    pub(crate) fn refresh_weights(&mut self) {
+        for i in 0..self.edges.len() {
+            let (source, target) = (self.edges[i].source, self.edges[i].target);
+            if self.parallel_edges[source].contains_key(&target) {
+                self.refresh_weight(source, target);
+            } else {
+                self.set_pair_weight(source, target, Some(self.edges[i].weight));
+            }
+        }
+        self.touch();
+    }
+}
+
+#[wasm_bindgen]
+impl Graph {
+    /// a multigraph keeps every added edge, otherwise adding an edge between two vertexes that
+    /// are already linked only updates the weight of the existing edge.
+    /// turning it off keeps the parallel edges added so far
+    pub fn set_multigraph(&mut self, is_multigraph: bool) {
+        self.is_multigraph = is_multigraph;
+    }
+
+    pub fn is_multigraph(&self) -> bool {
+        self.is_multigraph
+    }
+
+    /// keep-first, keep-last (default), sum, min or max. return false for an unknown name
+    pub fn set_collapse_policy(&mut self, name: &str) -> bool {
+        match CollapsePolicy::from_name(name) {
+            Some(policy) => {
+                self.collapse_policy = policy;
+                self.refresh_weights();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get_edges_len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn get_edge_id(&self, index: usize) -> usize {
+        self.edges[index].id
+    }
+
+    /// the edge ids stay the same when other edges are removed, indexes don't
+    pub fn get_edge_index(&self, id: usize) -> Option<usize> {
+        self.edges_map
+            .get(id)
+            .copied()
+            .filter(|index| *index != usize::MAX)
+    }
+
+    /// ids of the parallel edges from source to target in adding order, both directions for
+    /// undirected graphs
+    pub fn get_edge_ids_between(&self, source: &str, target: &str) -> Vec<u32> {
+        let (source, target) = match (self.vertexs_map.get(source), self.vertexs_map.get(target)) {
+            (Some(source), Some(target)) => (*source, *target),
+            _ => return vec![],
+        };
+        self.pair_edge_ids(source, target)
+            .iter()
+            .map(|id| *id as u32)
+            .collect()
+    }
+
+    pub fn remove_edge_by_id(&mut self, id: usize) -> Option<GraphRemoval> {
+        let index = self.get_edge_index(id)?;
+        let mut removal = GraphRemoval::default();
+        self.swap_remove_edges(vec![index], &mut removal);
+        Some(removal)
+    }
+}
@@ -0,0 +1,146 @@
+use crate::graph::Graph;
+use std::collections::VecDeque;
+use wasm_bindgen::prelude::*;
+
+/// a new graph cut out of a parent graph. vertexes and edges keep the parent order, their
+/// positions, weights and attributes are copied
+#[wasm_bindgen]
+pub struct Subgraph {
+    graph: Graph,
+    vertexes: Vec<usize>,
+    edges: Vec<usize>,
+}
+
+impl Subgraph {
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+}
+
+#[wasm_bindgen]
+impl Subgraph {
+    /// parent index of every subgraph vertex
+    pub fn parent_vertexes(&self) -> Vec<u32> {
+        self.vertexes.iter().map(|v| *v as u32).collect()
+    }
+
+    /// parent index of every subgraph edge
+    pub fn parent_edges(&self) -> Vec<u32> {
+        self.edges.iter().map(|e| *e as u32).collect()
+    }
+
+    /// take the graph out, the subgraph can't be used afterwards
+    pub fn into_graph(self) -> Graph {
+        self.graph
+    }
+}
+
+impl Graph {
+    /// `vertexes` ascending and unique, `edges` ascending with both ends in `vertexes`
+    pub fn make_subgraph(&self, vertexes: Vec<usize>, edges: Vec<usize>) -> Subgraph {
+        let mut graph = Graph::new();
+        graph.set_directed(self.is_directed);
+        graph.collapse_policy = self.collapse_policy;
+        let mut local = vec![usize::MAX; self.vertexes.len()];
+        for (i, v) in vertexes.iter().enumerate() {
+            local[*v] = i;
+            let position = self.get_vertex_position(*v);
+            graph.add_vertex(&self.vertexes[*v].id, position[0], position[1], position[2]);
+        }
+        // keep parallel edges of the parent as they are
+        graph.is_multigraph = true;
+        for e in &edges {
+            let edge = &self.edges[*e];
+            graph.add_edge_with_base(
+                local[edge.source],
+                local[edge.target],
+                edge.base_weight,
+                edge.weight,
+            );
+        }
+        graph.is_multigraph = self.is_multigraph;
+        graph.vertex_attributes = self.vertex_attributes.select(&vertexes);
+        graph.edge_attributes = self.edge_attributes.select(&edges);
+        Subgraph {
+            graph,
+            vertexes,
+            edges,
+        }
+    }
+
+    /// the vertexes and every edge between them
+    pub fn make_induced_subgraph(&self, vertexes: &[usize]) -> Subgraph {
+        let mut keep = vec![false; self.vertexes.len()];
+        vertexes.iter().for_each(|v| keep[*v] = true);
+        let edges = (0..self.edges.len())
+            .filter(|e| keep[self.edges[*e].source] && keep[self.edges[*e].target])
+            .collect();
+        let vertexes = (0..keep.len()).filter(|v| keep[*v]).collect();
+        self.make_subgraph(vertexes, edges)
+    }
+
+    /// the edges and their ends
+    pub fn make_edge_subgraph(&self, edges: &[usize]) -> Subgraph {
+        let mut keep = vec![false; self.edges.len()];
+        edges.iter().for_each(|e| keep[*e] = true);
+        let edges: Vec<usize> = (0..keep.len()).filter(|e| keep[*e]).collect();
+        let mut used = vec![false; self.vertexes.len()];
+        for e in &edges {
+            used[self.edges[*e].source] = true;
+            used[self.edges[*e].target] = true;
+        }
+        let vertexes = (0..used.len()).filter(|v| used[*v]).collect();
+        self.make_subgraph(vertexes, edges)
+    }
+
+    /// vertexes within `radius` hops of `center` following edges in both directions,
+    /// induced like `make_induced_subgraph`
+    pub fn make_ego_network(&self, center: usize, radius: usize) -> Subgraph {
+        let mut hops = vec![usize::MAX; self.vertexes.len()];
+        hops[center] = 0;
+        let mut queue = VecDeque::from(vec![center]);
+        while let Some(v) = queue.pop_front() {
+            if hops[v] == radius {
+                continue;
+            }
+            for u in self.neighbors_map[v]
+                .keys()
+                .chain(self.in_neighbor_weights(v).keys())
+            {
+                if hops[*u] == usize::MAX {
+                    hops[*u] = hops[v] + 1;
+                    queue.push_back(*u);
+                }
+            }
+        }
+        let vertexes: Vec<usize> = (0..hops.len()).filter(|v| hops[*v] != usize::MAX).collect();
+        self.make_induced_subgraph(&vertexes)
+    }
+}
+
+#[wasm_bindgen]
+impl Graph {
+    /// unknown ids are ignored
+    pub fn induced_subgraph(&self, ids: Vec<String>) -> Subgraph {
+        let vertexes: Vec<usize> = ids
+            .iter()
+            .filter_map(|id| self.vertexs_map.get(id).copied())
+            .collect();
+        self.make_induced_subgraph(&vertexes)
+    }
+
+    /// indexes outside the edges are ignored
+    pub fn edge_subgraph(&self, edges: &[u32]) -> Subgraph {
+        let edges: Vec<usize> = edges
+            .iter()
+            .map(|e| *e as usize)
+            .filter(|e| *e < self.edges.len())
+            .collect();
+        self.make_edge_subgraph(&edges)
+    }
+
+    pub fn ego_network(&self, id: &str, radius: usize) -> Option<Subgraph> {
+        let center = *self.vertexs_map.get(id)?;
+        Some(self.make_ego_network(center, radius))
+    }
+}
@@ -60,3 +60,1253 @@ fn test_nforce_3d() {
         t += 0.1
     }
 }
+
+#[test]
+fn test_similarity() {
+    use crate::graph::algos::similarity::{self, Similarity};
+    let graph = get_graph();
+    assert_eq!(
+        similarity::score(&graph, Similarity::CommonNeighbors, 1, 2),
+        1.
+    );
+    assert_eq!(similarity::score(&graph, Similarity::Jaccard, 1, 2), 1.);
+    assert_eq!(
+        similarity::score(&graph, Similarity::ResourceAllocation, 1, 2),
+        0.5
+    );
+    assert_eq!(
+        similarity::score(&graph, Similarity::PreferentialAttachment, 0, 1),
+        2.
+    );
+    let top = similarity::top_k(&graph, Similarity::AdamicAdar, 1, 5, true);
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0].0, 2);
+    assert!((top[0].1 - 1. / 2f32.ln()).abs() < 1e-6);
+
+    // u -> w <- v, the candidates come from the sources of the shared target
+    let mut graph = Graph::new();
+    graph.set_directed(true);
+    for id in ["u", "v", "w"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    graph.add_edge("u", "w", 1.);
+    graph.add_edge("v", "w", 1.);
+    assert_eq!(
+        similarity::score(&graph, Similarity::CommonNeighbors, 0, 1),
+        1.
+    );
+    let top = similarity::top_k(&graph, Similarity::CommonNeighbors, 0, 5, false);
+    assert_eq!(top, vec![(1, 1.)]);
+}
+
+#[test]
+fn test_simrank() {
+    use crate::graph::algos::{rwr, simrank};
+    let graph = get_graph();
+    let sim = simrank::make_simrank(&graph, 0.8, 10);
+    assert_eq!(sim[1][1], 1.);
+    assert!((sim[1][2] - 0.8).abs() < 1e-6);
+    let single = simrank::make_simrank_single_source(&graph, 1, 0.8, 5);
+    assert_eq!(single[1], 1.);
+    assert!(single[2] > 0.);
+    let p = rwr::make_rwr(&graph, 0, 0.15, 100);
+    assert!((p.iter().sum::<f32>() - 1.).abs() < 1e-4);
+    assert!(p[0] > p[1]);
+    assert!((p[1] - p[2]).abs() < 1e-6);
+}
+
+#[test]
+fn test_random_walk() {
+    use crate::graph::algos::random_walk;
+    let mut graph = get_graph();
+    graph.add_vertex("d", 0., 0., 0.);
+    let walks = random_walk::make_uniform_walks(&graph, 4, 2, 42);
+    assert_eq!(walks.len(), 4 * 4 * 2);
+    assert_eq!(walks, random_walk::make_uniform_walks(&graph, 4, 2, 42));
+    // a walk from a leaf always goes back to "a"
+    assert_eq!(walks[4..6], [1, 0]);
+    // "d" has no neighbors
+    assert_eq!(
+        walks[12..16],
+        [
+            3,
+            random_walk::WALK_PADDING,
+            random_walk::WALK_PADDING,
+            random_walk::WALK_PADDING
+        ]
+    );
+    let walks = random_walk::make_node2vec_walks(&graph, 3, 1, 1000., 0.001, 7);
+    // leaving "a" the walk never returns to the previous vertex while another choice exists
+    assert_eq!(walks[3..6], [1, 0, 2]);
+}
+
+#[test]
+fn test_spectral() {
+    use crate::graph::algos::spectral::{self, Laplacian, LaplacianKind};
+    let graph = get_graph();
+    let (connectivity, fiedler) = spectral::make_fiedler(&graph);
+    assert!((connectivity - 1.).abs() < 1e-6);
+    assert!(fiedler[0].abs() < 1e-6);
+    assert!((fiedler[1] - 0.5f64.sqrt()).abs() < 1e-6);
+    let laplacian = Laplacian::new(&graph, LaplacianKind::RandomWalk);
+    assert_eq!(laplacian.get(0, 1), -0.5);
+    // a long path needs restarted lanczos, lambda_2 = 2 - 2cos(pi / n)
+    let mut path = Graph::new();
+    for i in 0..150 {
+        path.add_vertex(&i.to_string(), 0., 0., 0.);
+        if i > 0 {
+            path.add_edge(&(i - 1).to_string(), &i.to_string(), 1.);
+        }
+    }
+    let (connectivity, _) = spectral::make_fiedler(&path);
+    let expected = 2. - 2. * (std::f64::consts::PI / 150.).cos();
+    assert!((connectivity - expected).abs() < 1e-5);
+}
+
+#[test]
+fn test_vf2() {
+    use crate::graph::algos::vf2::{self, MatchMode};
+    let make = |ids: &[&str], edges: &[(&str, &str)]| {
+        let mut graph = Graph::new();
+        graph.set_directed(true);
+        ids.iter().for_each(|id| graph.add_vertex(id, 0., 0., 0.));
+        edges.iter().for_each(|(s, t)| {
+            graph.add_edge(s, t, 1.);
+        });
+        graph
+    };
+    let target = make(
+        &["a", "b", "c", "d"],
+        &[("a", "b"), ("b", "c"), ("a", "c"), ("c", "d"), ("b", "d")],
+    );
+    let pattern = make(&["x", "y", "z"], &[("x", "y"), ("y", "z"), ("x", "z")]);
+    let matches = vf2::find_matches(
+        &pattern,
+        &target,
+        MatchMode::Monomorphism,
+        0,
+        |_, _| true,
+        |_, _| true,
+    );
+    assert_eq!(matches, vec![vec![0, 1, 2], vec![1, 2, 3]]);
+    let limited = vf2::find_matches(
+        &pattern,
+        &target,
+        MatchMode::Monomorphism,
+        1,
+        |_, _| true,
+        |_, _| true,
+    );
+    assert_eq!(limited.len(), 1);
+    let filtered = vf2::find_matches(
+        &pattern,
+        &target,
+        MatchMode::InducedSubgraph,
+        0,
+        |p, t| p != 0 || t != 0,
+        |_, _| true,
+    );
+    assert_eq!(filtered, vec![vec![1, 2, 3]]);
+    let path = make(&["x", "y", "z"], &[("x", "y"), ("y", "z")]);
+    assert_eq!(
+        vf2::find_matches(
+            &path,
+            &target,
+            MatchMode::InducedSubgraph,
+            0,
+            |_, _| true,
+            |_, _| true
+        ),
+        vec![vec![0, 1, 3], vec![0, 2, 3]]
+    );
+    assert_eq!(
+        vf2::find_matches(
+            &path,
+            &target,
+            MatchMode::Monomorphism,
+            0,
+            |_, _| true,
+            |_, _| true
+        )
+        .len(),
+        4
+    );
+}
+
+#[test]
+fn test_motif() {
+    use crate::graph::algos::motif;
+    let mut clique = Graph::new();
+    for id in ["a", "b", "c", "d"] {
+        clique.add_vertex(id, 0., 0., 0.);
+    }
+    for (s, t) in [
+        ("a", "b"),
+        ("a", "c"),
+        ("a", "d"),
+        ("b", "c"),
+        ("b", "d"),
+        ("c", "d"),
+    ] {
+        clique.add_edge(s, t, 1.);
+    }
+    assert_eq!(
+        motif::make_undirected_motifs(&clique),
+        vec![0, 4, 0, 0, 0, 0, 0, 1]
+    );
+    let gdv = motif::make_graphlet_degree_vectors(&clique);
+    assert_eq!(gdv[0], [3, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    // a -> b -> c -> a plus an isolated d
+    let mut cycle = Graph::new();
+    cycle.set_directed(true);
+    for id in ["a", "b", "c", "d"] {
+        cycle.add_vertex(id, 0., 0., 0.);
+    }
+    for (s, t) in [("a", "b"), ("b", "c"), ("c", "a")] {
+        cycle.add_edge(s, t, 1.);
+    }
+    let census = motif::make_triad_census(&cycle);
+    assert_eq!(census[9], 1); // 030C
+    assert_eq!(census[1], 3); // 012
+    assert_eq!(census.iter().sum::<u64>(), 4);
+    assert!(motif::make_directed_motifs4(&cycle).is_empty());
+}
+
+#[test]
+fn test_sssp() {
+    let mut graph = get_graph();
+    graph.add_vertex("d", 0., 0., 0.);
+    graph.add_edge("b", "d", 2.);
+    let result = graph.run_algo("sssp", "c");
+    assert_eq!(result, vec![1., 2., 0., 4.]);
+    let mut directed = Graph::new();
+    directed.set_directed(true);
+    for id in ["a", "b", "c"] {
+        directed.add_vertex(id, 0., 0., 0.);
+    }
+    directed.add_edge("a", "b", 4.);
+    directed.add_edge("a", "c", 1.);
+    directed.add_edge("c", "b", -2.);
+    assert_eq!(directed.run_algo("sssp", "a"), vec![0., -1., 1.]);
+}
+
+#[test]
+fn test_steiner() {
+    use crate::graph::algos::steiner;
+    let mut graph = get_graph();
+    graph.add_vertex("d", 0., 0., 0.);
+    graph.add_edge("b", "d", 1.);
+    graph.add_edge("c", "d", 5.);
+    let tree = steiner::make_steiner_tree(&graph, &[1, 2]);
+    assert_eq!(tree.edges(), vec![0, 1]);
+    assert_eq!(tree.vertexes(), vec![0]);
+    let tree = steiner::make_steiner_tree(&graph, &[2, 3]);
+    assert_eq!(tree.edges(), vec![0, 1, 2]);
+    assert_eq!(tree.vertexes(), vec![0, 1]);
+}
+
+#[test]
+fn test_tsp() {
+    use crate::graph::algos::tsp::{self, TspDistance};
+    let mut graph = Graph::new();
+    // corners of a square in a scrambled order
+    for (id, x, y) in [
+        ("a", 0., 0.),
+        ("b", 1., 1.),
+        ("c", 1., 0.),
+        ("d", 0., 1.),
+        ("e", 0.5, 0.),
+    ] {
+        graph.add_vertex(id, x, y, 0.);
+    }
+    let vertexes: Vec<usize> = (0..5).collect();
+    let dist = tsp::make_distance_matrix(&graph, &vertexes, TspDistance::Euclidean);
+    for construction in ["nearest_neighbor", "mst"] {
+        let tour = tsp::make_tour(
+            &graph,
+            &vertexes,
+            TspDistance::Euclidean,
+            construction,
+            true,
+        );
+        assert_eq!(tour[0], 0);
+        let indexes: Vec<usize> = tour.clone();
+        assert!((tsp::tour_length(&dist, &indexes, true) - 4.).abs() < 1e-5);
+    }
+    let open = tsp::make_tour(&graph, &vertexes, TspDistance::Euclidean, "mst", false);
+    assert!((tsp::tour_length(&dist, &open, false) - 3.).abs() < 1e-5);
+    let path = get_graph();
+    let tour = tsp::make_tour(&path, &[1, 0, 2], TspDistance::ShortestPath, "", false);
+    assert_eq!(tour, vec![1, 0, 2]);
+}
+
+#[test]
+fn test_covering() {
+    use crate::graph::algos::covering;
+    let mut graph = get_graph();
+    graph.add_vertex("d", 0., 0., 0.);
+    graph.add_vertex("e", 0., 0., 0.);
+    graph.add_edge("d", "e", 1.);
+    assert_eq!(covering::make_dominating_set(&graph), vec![0, 3]);
+    assert_eq!(covering::make_vertex_cover(&graph), vec![0, 1, 3, 4]);
+    assert_eq!(covering::make_independent_set(&graph), vec![1, 2, 3]);
+    assert_eq!(covering::independent_set(&graph), vec!["b", "c", "d"]);
+}
+
+#[test]
+fn test_stats() {
+    use crate::graph::algos::stats;
+    let graph = get_graph();
+    let summary = stats::make_stats(&graph);
+    assert_eq!(summary.max_degree, 2);
+    assert!((summary.density - 2. / 3.).abs() < 1e-6);
+    assert!((summary.assortativity + 1.).abs() < 1e-6);
+    assert_eq!(stats::degree_histogram(&graph), vec![0, 2, 1]);
+    let mut directed = get_graph();
+    directed.clear();
+    directed.set_directed(true);
+    for id in ["a", "b", "c"] {
+        directed.add_vertex(id, 0., 0., 0.);
+    }
+    directed.add_edge("a", "b", 1.);
+    directed.add_edge("a", "c", 1.);
+    assert_eq!(stats::out_degree_histogram(&directed), vec![2, 0, 1]);
+    assert_eq!(stats::in_degree_histogram(&directed), vec![1, 2]);
+    // a pure power law sample recovers its exponent
+    let samples: Vec<usize> = (1..2000)
+        .map(|i| (2000. / i as f64).powf(1. / 1.5) as usize)
+        .collect();
+    let (alpha, _, _) = stats::make_power_law_fit(&samples);
+    assert!((alpha - 2.5).abs() < 0.3);
+    // a discrete power law from 1 with many ties keeps the whole sample in the fit
+    let mut samples = vec![];
+    for k in 1..300usize {
+        let count = (10000. * (k as f64).powf(-2.5) / 1.341487).round() as usize;
+        samples.extend(std::iter::repeat_n(k, count));
+    }
+    let (alpha, x_min, ks) = stats::make_power_law_fit(&samples);
+    assert_eq!(x_min, 1);
+    assert!((alpha - 2.5).abs() < 0.05, "alpha {}", alpha);
+    assert!(ks < 0.01, "ks {}", ks);
+}
+
+#[test]
+fn test_reachability() {
+    use crate::graph::algos::reachability::Reachability;
+    let mut graph = Graph::new();
+    graph.set_directed(true);
+    for id in ["a", "b", "c", "d", "e"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    // a <-> b -> c -> d, e is isolated
+    graph.add_edge("a", "b", 1.);
+    graph.add_edge("b", "a", 1.);
+    graph.add_edge("b", "c", 1.);
+    graph.add_edge("c", "d", 1.);
+    let scc = graph.run_algo("scc", "");
+    assert_eq!(scc[0], scc[1]);
+    assert_ne!(scc[1], scc[2]);
+    let index = Reachability::new(&graph);
+    assert_eq!(index.components_len(), 4);
+    assert!(index.can_reach(0, 3));
+    assert!(!index.can_reach(3, 0));
+    assert!(!index.can_reach(0, 4));
+    assert_eq!(index.descendants(1), vec![0, 1, 2, 3]);
+    assert_eq!(index.ancestors(2), vec![0, 1, 2]);
+
+    // a long chain with a shortcut stays one interval per component
+    let mut chain = Graph::new();
+    chain.set_directed(true);
+    for i in 0..1000 {
+        chain.add_vertex(&i.to_string(), 0., 0., 0.);
+    }
+    for i in 0..999 {
+        chain.add_edge_by_index(i, i + 1, 1.);
+    }
+    chain.add_edge_by_index(10, 500, 1.);
+    let index = Reachability::new(&chain);
+    assert_eq!(index.intervals_len(), 1000);
+    assert!(index.can_reach(10, 999));
+    assert!(!index.can_reach(500, 10));
+    assert_eq!(index.descendants(997), vec![997, 998, 999]);
+}
+
+#[test]
+fn test_mincut_partition() {
+    use crate::graph::algos::{mincut, partition};
+    let mut graph = Graph::new();
+    for id in ["a", "b", "c", "d", "e", "f"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    // two triangles joined by c - d
+    for (s, t) in [
+        ("a", "b"),
+        ("b", "c"),
+        ("a", "c"),
+        ("d", "e"),
+        ("e", "f"),
+        ("d", "f"),
+        ("c", "d"),
+    ] {
+        graph.add_edge(s, t, 1.);
+    }
+    let cut = mincut::make_min_cut(&graph);
+    assert_eq!(cut.weight, 1.);
+    let side = cut.side();
+    assert!(side == vec![0, 1, 2] || side == vec![3, 4, 5]);
+    let parts = partition::make_partition(&graph, 2, 7);
+    assert_eq!(partition::make_edge_cut(&graph, &parts), 1.);
+    assert_eq!(parts.iter().filter(|p| **p == 0).count(), 3);
+    // a heavy c - d edge from an attribute keeps c and d together, the weights come back
+    graph.set_edge_number("weight", 6, 10.);
+    let parts = partition::partition_graph_by_weight(&mut graph, 2, 7, "weight");
+    assert_eq!(parts[2], parts[3]);
+    assert_eq!(graph.neighbors_map[2][&3], 1.);
+    assert!(partition::partition_graph_by_weight(&mut graph, 2, 7, "missing").is_empty());
+    for (v, group) in ["x", "x", "x", "y", "y", "y"].iter().enumerate() {
+        graph.set_vertex_string("group", v, group.to_string());
+    }
+    assert_eq!(partition::group_edge_cut(&graph, "group"), 1.);
+    assert!(partition::group_edge_cut(&graph, "missing").is_nan());
+
+    // a 20x20 grid split in 4 should stay close to the 40 edge optimum
+    let mut grid = Graph::new();
+    for i in 0..400 {
+        grid.add_vertex(&i.to_string(), 0., 0., 0.);
+    }
+    for i in 0..400 {
+        if i % 20 != 19 {
+            grid.add_edge(&i.to_string(), &(i + 1).to_string(), 1.);
+        }
+        if i < 380 {
+            grid.add_edge(&i.to_string(), &(i + 20).to_string(), 1.);
+        }
+    }
+    let parts = partition::make_partition(&grid, 4, 1);
+    for p in 0..4 {
+        let size = parts.iter().filter(|v| **v == p).count();
+        assert!((95..=105).contains(&size));
+    }
+    assert!(partition::make_edge_cut(&grid, &parts) <= 60.);
+}
+
+#[test]
+fn test_cycles() {
+    use crate::graph::algos::cycles;
+    let mut graph = Graph::new();
+    for id in ["a", "b", "c", "d"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    for s in ["a", "b", "c", "d"] {
+        for t in ["a", "b", "c", "d"] {
+            if s < t {
+                graph.add_edge(s, t, 1.);
+            }
+        }
+    }
+    // k4: 4 triangles and 3 squares, 6 - 4 + 1 fundamental cycles
+    assert_eq!(
+        cycles::make_simple_cycles(&graph, usize::MAX, usize::MAX, None).len(),
+        7
+    );
+    let basis = cycles::make_cycle_basis(&graph);
+    assert_eq!(basis.len(), 3);
+    for cycle in &basis {
+        for i in 0..cycle.len() {
+            let next = cycle[(i + 1) % cycle.len()];
+            assert!(graph.neighbors_map[cycle[i]].contains_key(&next));
+        }
+    }
+
+    // complete directed k4: 6 two-cycles, 8 three-cycles and 6 four-cycles
+    let mut directed = Graph::new();
+    directed.set_directed(true);
+    for id in ["a", "b", "c", "d"] {
+        directed.add_vertex(id, 0., 0., 0.);
+    }
+    for s in ["a", "b", "c", "d"] {
+        for t in ["a", "b", "c", "d"] {
+            if s != t {
+                directed.add_edge(s, t, 1.);
+            }
+        }
+    }
+    let all = cycles::make_simple_cycles(&directed, usize::MAX, usize::MAX, None);
+    assert_eq!(all.len(), 20);
+    assert!(all.iter().all(|c| c[0] == *c.iter().min().unwrap()));
+    assert_eq!(
+        cycles::make_simple_cycles(&directed, 3, usize::MAX, None).len(),
+        14
+    );
+    assert_eq!(
+        cycles::make_simple_cycles(&directed, usize::MAX, 5, None).len(),
+        5
+    );
+    let through = cycles::make_simple_cycles(&directed, usize::MAX, usize::MAX, Some(3));
+    assert_eq!(through.len(), 15);
+    assert!(through.iter().all(|c| c[0] == 3));
+}
+
+#[test]
+fn test_eulerian() {
+    use crate::graph::algos::eulerian;
+    // walk the trail and check every edge is used once and consecutive edges meet
+    fn check_trail(graph: &Graph, trail: &[usize], closed: bool) {
+        assert_eq!(trail.len(), graph.edges.len());
+        let mut sorted = trail.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), trail.len());
+        let first = &graph.edges[trail[0]];
+        let mut at = if graph.is_directed
+            || graph.edges.len() == 1
+            || [graph.edges[trail[1]].source, graph.edges[trail[1]].target].contains(&first.target)
+        {
+            first.source
+        } else {
+            first.target
+        };
+        let start = at;
+        for index in trail {
+            let edge = &graph.edges[*index];
+            at = if edge.source == at {
+                edge.target
+            } else {
+                assert!(!graph.is_directed && edge.target == at);
+                edge.source
+            };
+        }
+        assert_eq!(at == start, closed);
+    }
+
+    let empty = Graph::new();
+    assert!(!eulerian::has_eulerian_path(&empty));
+    assert!(eulerian::eulerian_circuit(&empty).is_empty());
+    assert!(eulerian::eulerian_path(&empty).is_empty());
+
+    // house: a square with a roof, only the bottom corners have odd degree
+    let mut graph = Graph::new();
+    for id in ["a", "b", "c", "d", "e"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    for (s, t) in [
+        ("a", "b"),
+        ("b", "c"),
+        ("c", "d"),
+        ("d", "a"),
+        ("c", "e"),
+        ("e", "d"),
+        ("a", "c"),
+    ] {
+        graph.add_edge(s, t, 1.);
+    }
+    assert!(!eulerian::has_eulerian_circuit(&graph));
+    assert!(eulerian::has_eulerian_path(&graph));
+    let trail = eulerian::make_eulerian_trail(&graph, false).unwrap();
+    check_trail(&graph, &trail, false);
+    assert!(eulerian::make_eulerian_trail(&graph, true).is_none());
+
+    let mut directed = Graph::new();
+    directed.set_directed(true);
+    for id in ["a", "b", "c", "d"] {
+        directed.add_vertex(id, 0., 0., 0.);
+    }
+    for (s, t) in [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d")] {
+        directed.add_edge(s, t, 1.);
+    }
+    let trail = eulerian::make_eulerian_trail(&directed, false).unwrap();
+    assert_eq!(trail, vec![2, 0, 1, 3]);
+    directed.add_edge("d", "c", 1.);
+    assert!(eulerian::has_eulerian_circuit(&directed));
+    check_trail(
+        &directed,
+        &eulerian::make_eulerian_trail(&directed, true).unwrap(),
+        true,
+    );
+    directed.add_edge("a", "d", 1.);
+    assert!(!eulerian::has_eulerian_circuit(&directed));
+    assert_eq!(eulerian::make_eulerian_start(&directed, false), Some(0));
+    check_trail(
+        &directed,
+        &eulerian::make_eulerian_trail(&directed, false).unwrap(),
+        false,
+    );
+}
+
+#[test]
+fn test_isomorphism() {
+    use crate::graph::algos::isomorphism;
+    let make = |edges: &[(usize, usize)]| {
+        let mut graph = Graph::new();
+        for i in 0..6 {
+            graph.add_vertex(&i.to_string(), 0., 0., 0.);
+        }
+        for (s, t) in edges {
+            graph.add_edge(&s.to_string(), &t.to_string(), 1.);
+        }
+        graph
+    };
+    let hexagon = make(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+    let shuffled = make(&[(3, 0), (0, 5), (5, 1), (1, 4), (4, 2), (2, 3)]);
+    let triangles = make(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+    let hash = isomorphism::make_wl_hash(&hexagon, None, 3);
+    assert_eq!(hash, isomorphism::make_wl_hash(&shuffled, None, 3));
+    let mapping = isomorphism::make_isomorphism(&hexagon, &shuffled, None, None).unwrap();
+    for edge in &hexagon.edges {
+        assert!(shuffled.neighbors_map[mapping[edge.source]].contains_key(&mapping[edge.target]));
+    }
+    // 1-wl can't tell a hexagon from two triangles, the exact check can
+    assert_eq!(hash, isomorphism::make_wl_hash(&triangles, None, 3));
+    assert!(isomorphism::make_isomorphism(&hexagon, &triangles, None, None).is_none());
+
+    let labels: Vec<String> = ["x", "y", "y", "y", "y", "y"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let moved: Vec<String> = ["y", "y", "y", "x", "y", "y"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_ne!(hash, isomorphism::make_wl_hash(&hexagon, Some(&labels), 3));
+    let mapping =
+        isomorphism::make_isomorphism(&hexagon, &shuffled, Some(&labels), Some(&moved)).unwrap();
+    assert_eq!(mapping[0], 3);
+    // short label lists pad with empty labels
+    let short = vec!["x".to_string()];
+    let padded: Vec<String> = ["x", "", "", "", "", ""]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_eq!(
+        isomorphism::wl_hash(&hexagon, 3, Some(short)),
+        isomorphism::make_wl_hash(&hexagon, Some(&padded), 3)
+    );
+}
+
+#[test]
+fn test_remove() {
+    let mut graph = Graph::new();
+    for (i, id) in ["a", "b", "c", "d"].iter().enumerate() {
+        graph.add_vertex(id, i as f32, 0., 0.);
+    }
+    graph.add_edge("a", "b", 1.);
+    graph.add_edge("b", "c", 2.);
+    graph.add_edge("c", "d", 3.);
+    graph.add_edge("d", "a", 4.);
+    let removal = graph.remove_vertex("b").unwrap();
+    // d moves into the slot of b, edges 0 and 1 go away
+    assert_eq!(removal.vertex_moves(), vec![3, 1]);
+    assert_eq!(removal.removed_edges(), vec![1, 0]);
+    assert_eq!(removal.edge_moves(), vec![3, 1, 2, 0]);
+    assert_eq!(graph.get_vertexes_len(), 3);
+    assert_eq!(graph.get_vertex_by_id("d"), Some(1));
+    assert_eq!(graph.get_vertex_position(1), vec![3., 0., 0.]);
+    assert_eq!(graph.get_nodes_position().len(), 9);
+    let ends: Vec<(usize, usize)> = graph.edges.iter().map(|e| (e.source, e.target)).collect();
+    assert_eq!(ends, vec![(2, 1), (1, 0)]);
+    assert_eq!(graph.neighbors_map[0].get(&1), Some(&4.));
+    assert_eq!(graph.neighbors_map[1].len(), 2);
+    assert!(graph.remove_vertex("b").is_none());
+
+    let removal = graph.remove_edge("a", "d").unwrap();
+    assert!(removal.edge_moves().is_empty());
+    assert_eq!(removal.removed_edges(), vec![1]);
+    assert!(graph.neighbors_map[0].is_empty());
+    assert!(!graph.neighbors_map[1].contains_key(&0));
+    assert!(graph.remove_edge("a", "d").is_none());
+    // d is vertex 1 and c is vertex 2 now
+    assert_eq!(graph.run_algo("apsp", "")[5], 3.);
+}
+
+#[test]
+fn test_attributes() {
+    use crate::graph::algos::{sssp, stats};
+    let mut graph = Graph::new();
+    for id in ["a", "b", "c", "d"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    graph.add_edge("a", "b", 1.);
+    graph.add_edge("b", "c", 1.);
+    graph.add_edge("c", "d", 1.);
+    for (i, group) in ["x", "x", "y", "y"].iter().enumerate() {
+        graph.set_vertex_string("group", i, group.to_string());
+    }
+    graph.set_vertex_numbers("embedding", 2, vec![0.5, 1.5]);
+    assert_eq!(graph.get_vertex_string("group", 3), Some("y".to_string()));
+    assert_eq!(
+        graph.get_vertex_numbers("embedding", 2),
+        Some(vec![0.5, 1.5])
+    );
+    assert_eq!(graph.get_vertex_numbers("embedding", 1), None);
+    assert_eq!(graph.get_vertex_number("group", 0), None);
+    assert_eq!(graph.get_vertex_groups("group"), vec![0, 0, 1, 1]);
+    assert!((stats::make_attribute_assortativity(&graph, "group") - 1. / 3.).abs() < 1e-6);
+    assert!(stats::make_attribute_assortativity(&graph, "missing").is_nan());
+
+    graph.set_edge_number_column("length", vec![5., f64::NAN, 2.]);
+    assert!(graph.get_edge_number_column("length")[1].is_nan());
+    assert!(graph.use_edge_weight("length"));
+    assert_eq!(sssp::make_sssp(&graph, 0).dist, vec![0., 5., 6., 8.]);
+    // switching columns and back is reversible
+    graph.set_edge_number_column("cost", vec![3., 3., 3.]);
+    assert!(graph.use_edge_weight("cost"));
+    assert_eq!(sssp::make_sssp(&graph, 0).dist, vec![0., 3., 6., 9.]);
+    graph.reset_edge_weight();
+    assert_eq!(sssp::make_sssp(&graph, 0).dist, vec![0., 1., 2., 3.]);
+    assert!(graph.use_edge_weight("length"));
+    assert_eq!(sssp::make_sssp(&graph, 0).dist, vec![0., 5., 6., 8.]);
+
+    // a vertex added later has no value, a removal keeps the columns aligned
+    graph.add_vertex("e", 0., 0., 0.);
+    assert_eq!(graph.get_vertex_string("group", 4), None);
+    graph.set_vertex_bool("flag", 4, true);
+    graph.remove_vertex("a");
+    assert_eq!(graph.get_vertex_bool("flag", 0), Some(true));
+    assert_eq!(graph.get_edge_number("length", 0), Some(2.));
+    assert_eq!(
+        graph.get_vertex_attribute_names(),
+        vec!["embedding", "flag", "group"]
+    );
+    graph.set_vertex_number("group", 0, 1.);
+    assert_eq!(graph.get_vertex_string("group", 1), None);
+
+    // writes past the end are ignored and don't replace a column of another type
+    assert!(!graph.set_vertex_string("group", 4, "z".to_string()));
+    assert!(!graph.set_edge_bool("new", 2, true));
+    assert_eq!(graph.get_vertex_number("group", 0), Some(1.));
+    assert_eq!(graph.get_edge_attribute_names(), vec!["cost", "length"]);
+}
+
+#[test]
+fn test_multigraph() {
+    let mut graph = Graph::new();
+    for id in ["a", "b", "c"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    // a simple graph updates the existing edge
+    let first = graph.add_edge("a", "b", 2.);
+    assert_eq!(graph.add_edge("b", "a", 3.), first);
+    assert_eq!(graph.get_edges_len(), 1);
+    assert_eq!(graph.neighbors_map[0][&1], 3.);
+    assert!(graph.parallel_edges.iter().all(|pairs| pairs.is_empty()));
+
+    graph.set_multigraph(true);
+    let second = graph.add_edge("a", "b", 5.);
+    let third = graph.add_edge("b", "a", 4.);
+    graph.add_edge("b", "c", 1.);
+    assert_eq!(graph.get_edges_len(), 4);
+    assert_eq!(
+        graph.get_edge_ids_between("b", "a"),
+        vec![first as u32, second as u32, third as u32]
+    );
+    assert_eq!(graph.neighbors_map[1][&0], 4.);
+    for (policy, weight) in [("keep-first", 3.), ("sum", 12.), ("min", 3.), ("max", 5.)] {
+        assert!(graph.set_collapse_policy(policy));
+        assert_eq!(graph.neighbors_map[0][&1], weight);
+        assert_eq!(graph.neighbors_map[1][&0], weight);
+    }
+    assert!(!graph.set_collapse_policy("mean"));
+
+    // ids survive the index shuffle of a removal
+    graph.remove_edge_by_id(second).unwrap();
+    assert_eq!(graph.neighbors_map[0][&1], 4.);
+    assert_eq!(graph.get_edge_index(third), Some(2));
+    assert_eq!(graph.get_edge_index(second), None);
+    graph.remove_edge("a", "b").unwrap();
+    assert!(!graph.neighbors_map[0].contains_key(&1));
+    assert_eq!(graph.get_edges_len(), 1);
+    assert_eq!(graph.get_edge_index(third), None);
+    graph.remove_vertex("a").unwrap();
+    // c took the index of a
+    assert_eq!(graph.get_edge_ids_between("c", "b").len(), 1);
+    assert_eq!(graph.neighbors_map[0][&1], 1.);
+}
+
+#[test]
+fn test_csr() {
+    let mut graph = Graph::new();
+    graph.set_directed(true);
+    for id in ["a", "b", "c", "d"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    graph.add_edge("c", "a", 3.);
+    graph.add_edge("a", "c", 1.);
+    graph.add_edge("a", "b", 2.);
+    graph.add_edge("b", "c", 4.);
+    let csr = graph.freeze();
+    assert_eq!(csr.offsets(), vec![0, 2, 3, 4, 4]);
+    assert_eq!(csr.targets(), vec![1, 2, 2, 0]);
+    assert_eq!(csr.weights(), vec![2., 1., 4., 3.]);
+    assert_eq!(csr.in_neighbors(2), &[0, 1]);
+    assert_eq!(csr.in_weights(2), &[1., 4.]);
+    assert_eq!(csr.in_degree(3), 0);
+    assert_eq!(csr.arcs_len(), 4);
+    let undirected = csr.undirected();
+    assert_eq!(undirected.out_neighbors(0), &[1, 2]);
+    assert_eq!(undirected.out_weights(2), &[3., 4.]);
+    assert_eq!(undirected.in_neighbors(2), &[0, 1]);
+    let order = graph.run_algo("bfs", "b");
+    assert_eq!(order, vec![1., 2., 0.]);
+    // the snapshot is shared until the graph changes
+    let snapshot = graph.snapshot();
+    assert!(std::rc::Rc::ptr_eq(&snapshot, &graph.snapshot()));
+    graph.add_edge("d", "a", 1.);
+    assert!(!std::rc::Rc::ptr_eq(&snapshot, &graph.snapshot()));
+    assert_eq!(graph.snapshot().out_neighbors(3), &[0]);
+    graph.set_directed(false);
+    assert_eq!(graph.snapshot().out_neighbors(3), &[0]);
+    assert_eq!(graph.snapshot().out_neighbors(0), &[1, 2, 3]);
+}
+
+#[test]
+fn test_directed_model() {
+    let mut graph = Graph::new();
+    graph.set_directed(true);
+    for id in ["a", "b", "c"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    graph.add_edge("a", "b", 1.);
+    graph.add_edge("a", "c", 1.);
+    graph.add_edge("c", "c", 1.);
+    assert_eq!((graph.out_degree(0), graph.in_degree(0)), (2, 0));
+    assert_eq!((graph.out_degree(2), graph.in_degree(2)), (1, 2));
+    assert_eq!(graph.degree(2), 3);
+    let mut in_neighbors = graph.get_in_neighbors(2);
+    in_neighbors.sort_unstable();
+    assert_eq!(in_neighbors, vec![0, 2]);
+    // counting again doesn't change anything
+    graph.calc_degree();
+    graph.calc_degree();
+    assert_eq!(graph.vertexes[0].degree, 2);
+
+    graph.set_directed(false);
+    assert!(graph.neighbors_map[1].contains_key(&0));
+    assert_eq!((graph.out_degree(1), graph.in_degree(1)), (1, 1));
+    assert_eq!(graph.get_in_neighbors(1), vec![0]);
+    // undirected graphs read their in neighbors from neighbors_map
+    assert!(graph.in_neighbors_map.is_empty());
+    graph.set_directed(true);
+    assert!(!graph.neighbors_map[1].contains_key(&0));
+    assert!(graph.get_in_neighbors(0).is_empty());
+
+    graph.remove_edge("a", "c").unwrap();
+    assert_eq!(graph.out_degree(0), 1);
+    assert_eq!(graph.get_in_neighbors(2), vec![2]);
+    graph.remove_vertex("a").unwrap();
+    // c took the index of a
+    assert_eq!((graph.degree(0), graph.in_degree(1)), (2, 0));
+}
+
+#[test]
+fn test_from_arrays() {
+    let positions = [0., 0., 0., 1., 0., 0., 0., 1., 0.5];
+    let graph = Graph::from_arrays(
+        &positions,
+        &[0, 1, 2, 7],
+        &[1, 2, 0, 0],
+        Some(vec![2., 3.]),
+        None,
+        true,
+    )
+    .unwrap();
+    assert_eq!(graph.get_vertexes_len(), 3);
+    assert_eq!(graph.get_vertex_id(2), "2");
+    assert_eq!(graph.get_vertex_position(2), vec![0., 1., 0.5]);
+    // the pair pointing to vertex 7 is skipped, the missing third weight is 1
+    assert_eq!(graph.get_edges_len(), 3);
+    assert_eq!(graph.neighbors_map[1][&2], 3.);
+    assert_eq!(graph.neighbors_map[2][&0], 1.);
+    assert_eq!(graph.in_degree(0), 1);
+
+    let ids: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+    let mut graph =
+        Graph::from_arrays(&positions, &[0], &[1], None, Some(ids.clone()), false).unwrap();
+    assert_eq!(graph.get_vertex_by_id("c"), Some(2));
+    assert!(graph.neighbors_map[1].contains_key(&0));
+    assert!(graph.add_edges(&[2], &[1], None));
+    assert_eq!(graph.degree(1), 2);
+    // sources and targets of different lengths are rejected
+    assert!(!graph.add_edges(&[2, 0], &[0], None));
+    assert_eq!(graph.get_edges_len(), 2);
+    assert!(Graph::from_arrays(&positions, &[0, 1], &[1], None, Some(ids), false).is_none());
+    // a short id list falls back to the index
+    graph.add_vertexes(&[0.; 6], Some(vec!["d".to_string()]));
+    assert_eq!(graph.get_vertex_id(3), "d");
+    assert_eq!(graph.get_vertex_id(4), "4");
+    // unless that id is taken
+    graph.add_vertex("6", 0., 0., 0.);
+    graph.add_vertexes(&[0.; 6], None);
+    assert_eq!(graph.get_vertex_id(6), "7");
+    assert_eq!(graph.get_vertex_id(7), "8");
+    assert_eq!(graph.get_vertex_by_id("6"), Some(5));
+}
+
+#[test]
+fn test_buffer_ptr() {
+    let mut graph = get_graph();
+    let edges = unsafe { std::slice::from_raw_parts(graph.get_edges_ptr(), 4) }.to_vec();
+    assert_eq!(edges, vec![0, 1, 0, 2]);
+    let weights = unsafe { std::slice::from_raw_parts(graph.get_edge_weights_ptr(), 2) }.to_vec();
+    assert_eq!(weights, vec![1., 1.]);
+    let degrees = unsafe { std::slice::from_raw_parts(graph.get_degrees_ptr(), 3) }.to_vec();
+    assert_eq!(degrees, vec![2, 1, 1]);
+    // an unchanged graph hands out the same buffer, a change refills it
+    assert_eq!(graph.get_edges_ptr(), graph.get_edges_ptr());
+    graph.add_edge("b", "c", 3.);
+    let edges = unsafe { std::slice::from_raw_parts(graph.get_edges_ptr(), 6) }.to_vec();
+    assert_eq!(edges, vec![0, 1, 0, 2, 1, 2]);
+    graph.set_edge_number_column("length", vec![5., 6., 7.]);
+    graph.use_edge_weight("length");
+    let weights = unsafe { std::slice::from_raw_parts(graph.get_edge_weights_ptr(), 3) }.to_vec();
+    assert_eq!(weights, vec![5., 6., 7.]);
+    graph.remove_vertex("a");
+    let degrees = unsafe { std::slice::from_raw_parts(graph.get_degrees_ptr(), 2) }.to_vec();
+    assert_eq!(degrees, vec![1, 1]);
+    let result = graph.run_algo("scc", "");
+    let len = graph.get_last_result_len();
+    let last = unsafe { std::slice::from_raw_parts(graph.get_last_result_ptr(), len) };
+    assert_eq!(last, &result[..]);
+}
+
+#[test]
+fn test_subgraph() {
+    let mut graph = Graph::new();
+    graph.set_directed(true);
+    for (i, id) in ["a", "b", "c", "d", "e"].iter().enumerate() {
+        graph.add_vertex(id, i as f32, 0., 0.);
+    }
+    graph.add_edge("a", "b", 1.);
+    graph.add_edge("b", "c", 2.);
+    graph.add_edge("d", "c", 3.);
+    graph.add_edge("d", "e", 4.);
+    graph.set_vertex_string("name", 3, "dee".to_string());
+    graph.set_edge_number("cost", 2, 30.);
+
+    let sub = graph.induced_subgraph(vec!["d".to_string(), "c".to_string(), "x".to_string()]);
+    assert_eq!(sub.parent_vertexes(), vec![2, 3]);
+    assert_eq!(sub.parent_edges(), vec![2]);
+    let sub = sub.into_graph();
+    assert!(sub.is_directed());
+    assert_eq!(sub.get_vertex_id(1), "d");
+    assert_eq!(sub.get_vertex_position(1), vec![3., 0., 0.]);
+    assert_eq!(sub.get_vertex_string("name", 1), Some("dee".to_string()));
+    assert_eq!(sub.get_edge_number("cost", 0), Some(30.));
+    assert_eq!(sub.get_neighbors(1), vec![0]);
+
+    // an attribute weight stays switchable in the subgraph
+    graph.use_edge_weight("cost");
+    let mut sub = graph
+        .induced_subgraph(vec!["d".to_string(), "c".to_string()])
+        .into_graph();
+    assert_eq!(sub.neighbor_weights(1)[&0], 30.);
+    sub.reset_edge_weight();
+    assert_eq!(sub.neighbor_weights(1)[&0], 3.);
+    graph.reset_edge_weight();
+
+    let sub = graph.edge_subgraph(&[3, 0, 9]);
+    assert_eq!(sub.parent_vertexes(), vec![0, 1, 3, 4]);
+    assert_eq!(sub.parent_edges(), vec![0, 3]);
+
+    // edges are followed in both directions
+    let ego = graph.ego_network("c", 1).unwrap();
+    assert_eq!(ego.parent_vertexes(), vec![1, 2, 3]);
+    assert_eq!(ego.parent_edges(), vec![1, 2]);
+    let ego = graph.ego_network("c", 2).unwrap();
+    assert_eq!(ego.parent_vertexes(), vec![0, 1, 2, 3, 4]);
+    assert!(graph.ego_network("x", 1).is_none());
+}
+
+#[test]
+fn test_transform() {
+    use crate::graph::algos::transform::*;
+    let mut path = Graph::new();
+    for id in ["a", "b", "c"] {
+        path.add_vertex(id, 0., 0., 0.);
+    }
+    path.add_edge("a", "b", 2.);
+    path.add_edge("b", "c", 3.);
+    let edges_len = |graph: &Graph| graph.get_edges_len();
+
+    let mut directed = make_transpose(&path);
+    directed.set_directed(true);
+    assert_eq!(directed.get_neighbors(1), vec![0]);
+    let reversed = make_transpose(&directed);
+    assert_eq!(reversed.get_neighbors(1), vec![2]);
+    directed.set_edge_number("len", 0, 7.);
+    directed.use_edge_weight("len");
+    let mut reversed = make_transpose(&directed);
+    assert_eq!(reversed.neighbor_weights(0)[&1], 7.);
+    reversed.reset_edge_weight();
+    assert_eq!(reversed.neighbor_weights(0)[&1], 2.);
+
+    let complement = make_complement(&path);
+    assert_eq!(edges_len(&complement), 1);
+    assert_eq!(complement.get_neighbors(0), vec![2]);
+
+    let line = make_line_graph(&path);
+    assert_eq!(line.get_vertexes_len(), 2);
+    assert_eq!(edges_len(&line), 1);
+
+    let two = make_disjoint_union(&path, &path, "x", "y");
+    assert_eq!(two.get_vertex_id(3), "ya");
+    assert_eq!(edges_len(&two), 4);
+
+    let mut other = Graph::new();
+    for id in ["b", "c", "d"] {
+        other.add_vertex(id, 0., 0., 0.);
+    }
+    other.add_edge("c", "b", 5.);
+    other.add_edge("c", "d", 1.);
+    let union = make_union(&path, &other);
+    assert_eq!(union.get_vertexes_len(), 4);
+    assert_eq!(edges_len(&union), 3);
+    let both = make_intersection(&path, &other);
+    assert_eq!(both.get_vertexes_len(), 2);
+    assert_eq!(both.edges[0].weight, 3.);
+    let only = make_difference(&path, &other);
+    assert_eq!(only.get_vertexes_len(), 3);
+    assert_eq!(edges_len(&only), 1);
+
+    // a path of 3 times a path of 3 is a 3x3 grid
+    let grid = make_cartesian_product(&path, &path);
+    assert_eq!(grid.get_vertexes_len(), 9);
+    assert_eq!(edges_len(&grid), 12);
+    assert_eq!(grid.get_vertex_id(5), "b,c");
+    let tensor = make_tensor_product(&path, &path);
+    assert_eq!(edges_len(&tensor), 8);
+    assert_eq!(tensor.neighbors_map[4][&8], 9.);
+
+    // union keeps the vertex attributes of the graph each vertex comes from
+    path.set_vertex_string("name", 0, "ay".to_string());
+    other.set_vertex_string("name", 2, "dee".to_string());
+    let union = make_union(&path, &other);
+    assert_eq!(union.get_vertex_string("name", 0), Some("ay".to_string()));
+    assert_eq!(union.get_vertex_string("name", 3), Some("dee".to_string()));
+
+    // a directed factor makes the product directed, undirected factors link both ways
+    let mut arrow = Graph::new();
+    arrow.set_directed(true);
+    arrow.add_vertex("x", 0., 0., 0.);
+    arrow.add_vertex("y", 0., 0., 0.);
+    arrow.add_edge("x", "y", 1.);
+    let mut pair = Graph::new();
+    pair.add_vertex("p", 0., 0., 0.);
+    pair.add_vertex("q", 0., 0., 0.);
+    pair.add_edge("p", "q", 1.);
+    assert_eq!(edges_len(&make_cartesian_product(&arrow, &pair)), 6);
+    assert_eq!(edges_len(&make_tensor_product(&arrow, &pair)), 2);
+
+    // sparse factors stay cheap
+    let mut long = Graph::new();
+    for i in 0..120 {
+        long.add_vertex(&i.to_string(), 0., 0., 0.);
+    }
+    for i in 0..119 {
+        long.add_edge_by_index(i, i + 1, 1.);
+    }
+    assert_eq!(edges_len(&make_tensor_product(&long, &long)), 2 * 119 * 119);
+    assert_eq!(
+        edges_len(&make_cartesian_product(&long, &long)),
+        2 * 120 * 119
+    );
+}
+
+#[test]
+fn test_contraction() {
+    let mut graph = Graph::new();
+    graph.set_directed(true);
+    for (i, id) in ["a", "b", "c", "d"].iter().enumerate() {
+        graph.add_vertex(id, i as f32 * 10., 0., 0.);
+    }
+    graph.add_edge("a", "b", 1.);
+    graph.add_edge("b", "c", 2.);
+    graph.add_edge("d", "c", 3.);
+    graph.add_edge("b", "d", 4.);
+    graph.add_edge("c", "d", 5.);
+    graph.set_vertex_string("name", 2, "cee".to_string());
+    graph.set_edge_number("cost", 2, 30.);
+
+    assert!(graph.contract(vec!["b".to_string()], "a").is_none());
+    let contraction = graph
+        .contract(vec!["b".to_string(), "c".to_string()], "bc")
+        .unwrap();
+    assert_eq!(
+        contraction.members(),
+        vec!["b".to_string(), "c".to_string()]
+    );
+    assert_eq!(graph.get_vertexes_len(), 3);
+    let meta = graph.get_vertex_by_id("bc").unwrap();
+    assert_eq!(graph.get_vertex_position(meta), vec![15., 0., 0.]);
+    let d = graph.get_vertex_by_id("d").unwrap();
+    let a = graph.get_vertex_by_id("a").unwrap();
+    assert_eq!(graph.neighbors_map[meta][&d], 9.);
+    assert_eq!(graph.neighbors_map[d][&meta], 3.);
+    assert_eq!(graph.neighbors_map[a][&meta], 1.);
+    assert_eq!(graph.get_edges_len(), 3);
+
+    let mut nforce = crate::graph::layouts::nforce_3d::NForce::new();
+    nforce.calc_link_bias(&mut graph);
+    nforce.update(&mut graph, 0.5);
+    let moved = graph.get_vertex_position(graph.get_vertex_by_id("bc").unwrap());
+
+    assert!(graph.expand(&contraction));
+    assert!(!graph.expand(&contraction));
+    assert_eq!(graph.get_vertexes_len(), 4);
+    assert_eq!(graph.get_edges_len(), 5);
+    let c = graph.get_vertex_by_id("c").unwrap();
+    assert_eq!(graph.get_vertex_position(c)[0], moved[0] + 5.);
+    assert_eq!(graph.get_vertex_string("name", c), Some("cee".to_string()));
+    let d = graph.get_vertex_by_id("d").unwrap();
+    assert_eq!(graph.neighbors_map[d][&c], 3.);
+    let cost = graph.get_edge_number_column("cost");
+    assert_eq!(cost.iter().filter(|v| **v == 30.).count(), 1);
+
+    // a column that changed type while contracted keeps its other rows
+    let contraction = graph
+        .contract(vec!["c".to_string(), "d".to_string()], "cd")
+        .unwrap();
+    let a = graph.get_vertex_by_id("a").unwrap();
+    graph.set_vertex_number("name", a, 1.);
+    assert!(graph.expand(&contraction));
+    let a = graph.get_vertex_by_id("a").unwrap();
+    let c = graph.get_vertex_by_id("c").unwrap();
+    assert_eq!(graph.get_vertex_number("name", a), Some(1.));
+    assert_eq!(graph.get_vertex_number("name", c), None);
+    let mut rows = crate::graph::attributes::Attributes::default();
+    rows.push();
+    rows.set_string("name", 0, "x".to_string());
+    assert_eq!(
+        graph.vertex_attributes.paste(a, &rows),
+        vec!["name".to_string()]
+    );
+    assert_eq!(graph.get_vertex_number("name", a), Some(1.));
+
+    // attribute weights stay switchable and the index changes are reported
+    let mut graph = Graph::new();
+    for id in ["a", "b", "c"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    graph.add_edge("a", "b", 1.);
+    graph.add_edge("b", "c", 1.);
+    graph.set_edge_number("len", 0, 7.);
+    graph.set_edge_number("len", 1, 9.);
+    graph.use_edge_weight("len");
+    let mut ids: Vec<String> = (0..3).map(|v| graph.get_vertex_id(v)).collect();
+    let contraction = graph.contract(vec!["a".to_string()], "m").unwrap();
+    for pair in contraction.removal().vertex_moves().chunks(2) {
+        ids[pair[1] as usize] = ids[pair[0] as usize].clone();
+    }
+    ids.truncate(2);
+    ids.push("m".to_string());
+    assert_eq!(
+        ids,
+        (0..3).map(|v| graph.get_vertex_id(v)).collect::<Vec<_>>()
+    );
+    assert!(graph.expand(&contraction));
+    let weights = |graph: &Graph| {
+        let mut weights: Vec<f32> = graph.edges.iter().map(|edge| edge.weight).collect();
+        weights.sort_by(f32::total_cmp);
+        weights
+    };
+    assert_eq!(weights(&graph), vec![7., 9.]);
+    graph.reset_edge_weight();
+    assert_eq!(weights(&graph), vec![1., 1.]);
+
+    // one meta vertex per value, vertexes without a value stay
+    let mut graph = Graph::new();
+    for id in ["a", "b", "c", "d"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    graph.add_edge("a", "b", 1.);
+    graph.add_edge("b", "c", 1.);
+    graph.add_edge("c", "d", 1.);
+    for (v, group) in [(0, "x"), (1, "x"), (3, "y")] {
+        graph.set_vertex_string("group", v, group.to_string());
+    }
+    let contractions = graph.contract_groups("group");
+    assert_eq!(
+        contractions.iter().map(|c| c.meta_id()).collect::<Vec<_>>(),
+        vec!["group=x", "group=y"]
+    );
+    assert_eq!(graph.get_vertexes_len(), 3);
+    let c = graph.get_vertex_by_id("c").unwrap();
+    assert_eq!(graph.neighbors_map[c].len(), 2);
+    for contraction in contractions.iter().rev() {
+        assert!(graph.expand(contraction));
+    }
+    assert_eq!(graph.get_vertexes_len(), 4);
+    assert_eq!(graph.get_edges_len(), 3);
+    assert!(graph.contract_groups("missing").is_empty());
+}
+
+#[test]
+fn test_remove_consistency() {
+    use crate::graph::utils::random::SeededRng;
+    for is_directed in [true, false] {
+        let mut rng = SeededRng::new(7);
+        let mut graph = Graph::new();
+        graph.set_directed(is_directed);
+        graph.set_multigraph(true);
+        for i in 0..40 {
+            graph.add_vertex(&i.to_string(), 0., 0., 0.);
+        }
+        for _ in 0..160 {
+            let (s, t) = (rng.next_index(40), rng.next_index(40));
+            graph.add_edge_by_index(s, t, 1. + rng.next_index(5) as f32);
+        }
+        for _ in 0..25 {
+            let v = rng.next_index(graph.get_vertexes_len());
+            let id = graph.get_vertex_id(v);
+            graph.remove_vertex(&id).unwrap();
+        }
+        // the same graph built from the remaining edges
+        let mut fresh = Graph::new();
+        fresh.set_directed(is_directed);
+        fresh.set_multigraph(true);
+        for v in 0..graph.get_vertexes_len() {
+            fresh.add_vertex(&graph.get_vertex_id(v), 0., 0., 0.);
+        }
+        for edge in &graph.edges {
+            fresh.add_edge_by_index(edge.source, edge.target, edge.weight);
+        }
+        assert_eq!(graph.neighbors_map, fresh.neighbors_map);
+        assert_eq!(graph.in_neighbors_map, fresh.in_neighbors_map);
+        for v in 0..graph.get_vertexes_len() {
+            assert_eq!(graph.get_vertex_by_id(&graph.get_vertex_id(v)), Some(v));
+            assert_eq!(graph.degree(v), fresh.degree(v));
+            assert_eq!(graph.incident_edges[v].len(), fresh.incident_edges[v].len());
+            for w in graph.get_neighbors(v) {
+                assert_eq!(
+                    graph.pair_edge_ids(v, w).len(),
+                    fresh.pair_edge_ids(v, w).len()
+                );
+            }
+            // only pairs linked more than once keep an id list
+            assert!(graph.parallel_edges[v].values().all(|ids| ids.len() > 1));
+        }
+    }
+}
+
+#[test]
+fn test_rebuild_keeps_adding_order() {
+    let mut graph = Graph::new();
+    graph.set_directed(true);
+    graph.set_multigraph(true);
+    for id in ["a", "b", "c"] {
+        graph.add_vertex(id, 0., 0., 0.);
+    }
+    graph.add_edge("b", "c", 1.);
+    let first = graph.add_edge("a", "b", 4.);
+    let last = graph.add_edge("a", "b", 2.);
+    // the swap moves the last edge in front of the first one
+    graph.remove_edge("b", "c");
+    graph.set_directed(false);
+    assert_eq!(
+        graph.get_edge_ids_between("a", "b"),
+        vec![first as u32, last as u32]
+    );
+    assert_eq!(graph.get_neighbors(0), vec![1]);
+    assert_eq!(graph.neighbor_weights(0)[&1], 2.);
+    graph.set_collapse_policy("keep-first");
+    assert_eq!(graph.neighbor_weights(0)[&1], 4.);
+}
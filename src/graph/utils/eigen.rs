@@ -0,0 +1,195 @@
+use crate::graph::utils::random::SeededRng;
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn normalize(v: &mut [f64]) -> f64 {
+    let norm = dot(v, v).sqrt();
+    if norm > 0. {
+        v.iter_mut().for_each(|x| *x /= norm);
+    }
+    norm
+}
+
+/// remove the components along `basis` twice, once is not enough when the basis is nearly dependent
+fn orthogonalize(v: &mut [f64], basis: &[Vec<f64>]) {
+    for _ in 0..2 {
+        for b in basis {
+            let d = dot(v, b);
+            v.iter_mut().zip(b).for_each(|(x, y)| *x -= d * y);
+        }
+    }
+}
+
+/// cyclic jacobi rotations for a dense symmetric matrix,
+/// return eigenvalues and eigenvectors (as columns of the second matrix) in the input order
+pub fn jacobi_eigen(mut a: Vec<Vec<f64>>) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = a.len();
+    let mut v = vec![vec![0.; n]; n];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.;
+    }
+    for _ in 0..100 {
+        let mut off = 0.;
+        for (i, row) in a.iter().enumerate() {
+            off += row[(i + 1)..].iter().map(|x| x * x).sum::<f64>();
+        }
+        if off < 1e-22 {
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2. * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.).sqrt());
+                let c = 1. / (t * t + 1.).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (head, tail) = a.split_at_mut(q);
+                for (apk, aqk) in head[p].iter_mut().zip(tail[0].iter_mut()) {
+                    let (x, y) = (*apk, *aqk);
+                    *apk = c * x - s * y;
+                    *aqk = s * x + c * y;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a[i][i]).collect(), v)
+}
+
+/// one lanczos run with full reorthogonalization from `start`, return the ritz pairs in descending order
+fn lanczos_run<F>(
+    start: Vec<f64>,
+    k: usize,
+    steps: usize,
+    rng: &mut SeededRng,
+    apply: &mut F,
+) -> (Vec<f64>, Vec<Vec<f64>>)
+where
+    F: FnMut(&[f64], &mut [f64]),
+{
+    let n = start.len();
+    let mut basis: Vec<Vec<f64>> = Vec::with_capacity(steps);
+    let mut alphas = vec![];
+    let mut betas = vec![];
+    let mut v = start;
+    normalize(&mut v);
+    let mut w = vec![0.; n];
+    while basis.len() < steps {
+        apply(&v, &mut w);
+        let alpha = dot(&w, &v);
+        basis.push(v);
+        orthogonalize(&mut w, &basis);
+        alphas.push(alpha);
+        if basis.len() == steps {
+            break;
+        }
+        let mut beta = normalize(&mut w);
+        if beta < 1e-10 {
+            // invariant subspace found, continue from a fresh random direction
+            beta = 0.;
+            loop {
+                w = (0..n).map(|_| rng.next_f64() - 0.5).collect();
+                orthogonalize(&mut w, &basis);
+                if normalize(&mut w) > 1e-10 {
+                    break;
+                }
+            }
+        }
+        betas.push(beta);
+        v = std::mem::replace(&mut w, vec![0.; n]);
+    }
+    let m = basis.len();
+    let mut t = vec![vec![0.; m]; m];
+    for i in 0..m {
+        t[i][i] = alphas[i];
+        if i + 1 < m {
+            t[i][i + 1] = betas[i];
+            t[i + 1][i] = betas[i];
+        }
+    }
+    let (values, vectors) = jacobi_eigen(t);
+    let mut order: Vec<usize> = (0..m).collect();
+    order.sort_by(|a, b| values[*b].total_cmp(&values[*a]));
+    let mut ritz_values = vec![];
+    let mut ritz_vectors = vec![];
+    for col in order.into_iter().take(k) {
+        let mut x = vec![0.; n];
+        for (i, b) in basis.iter().enumerate() {
+            let coef = vectors[i][col];
+            x.iter_mut().zip(b).for_each(|(x, y)| *x += coef * y);
+        }
+        normalize(&mut x);
+        ritz_values.push(values[col]);
+        ritz_vectors.push(x);
+    }
+    (ritz_values, ritz_vectors)
+}
+
+/// lanczos with full reorthogonalization for the `k` largest eigenpairs of a symmetric operator,
+/// `apply(x, y)` must write the product of the operator and x into y.
+/// when `steps` is smaller than n the run is restarted from the sum of the ritz vectors
+/// until every residual is below `tol` or `max_restarts` is reached.
+/// return eigenvalues in descending order and the matching unit eigenvectors
+pub fn lanczos_largest<F>(
+    n: usize,
+    k: usize,
+    steps: usize,
+    max_restarts: usize,
+    tol: f64,
+    seed: u64,
+    mut apply: F,
+) -> (Vec<f64>, Vec<Vec<f64>>)
+where
+    F: FnMut(&[f64], &mut [f64]),
+{
+    let k = k.min(n);
+    let steps = steps.max(k).min(n);
+    if k == 0 {
+        return (vec![], vec![]);
+    }
+    let mut rng = SeededRng::new(seed);
+    let mut start: Vec<f64> = (0..n).map(|_| rng.next_f64() - 0.5).collect();
+    let mut w = vec![0.; n];
+    let mut restarts = 0;
+    loop {
+        let (values, vectors) = lanczos_run(start, k, steps, &mut rng, &mut apply);
+        if steps == n || restarts >= max_restarts {
+            return (values, vectors);
+        }
+        let mut converged = true;
+        for (value, x) in values.iter().zip(&vectors) {
+            apply(x, &mut w);
+            let residual: f64 = w
+                .iter()
+                .zip(x)
+                .map(|(a, b)| (a - value * b).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            if residual > tol {
+                converged = false;
+                break;
+            }
+        }
+        if converged {
+            return (values, vectors);
+        }
+        start = vec![0.; n];
+        for x in &vectors {
+            start.iter_mut().zip(x).for_each(|(s, v)| *s += v);
+        }
+        restarts += 1;
+    }
+}
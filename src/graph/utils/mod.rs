@@ -1,2 +1,5 @@
+pub mod disjoint_set;
+pub mod eigen;
 pub mod octree;
 pub mod quadtree;
+pub mod random;
@@ -0,0 +1,29 @@
+/// splitmix64, a tiny seedable generator that yields the same sequence on wasm and native builds,
+/// `rand::random` can't be replayed so algorithms that must be reproducible use this instead
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> SeededRng {
+        SeededRng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// uniform in [0, n), n must be greater than 0
+    pub fn next_index(&mut self, n: usize) -> usize {
+        (self.next_f64() * n as f64) as usize
+    }
+}
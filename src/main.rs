@@ -1,3 +1,114 @@
+use rs_algos::graph::{
+    algos::{
+        bfs,
+        sssp::{self, State},
+    },
+    utils::random::SeededRng,
+    Graph,
+};
+use std::collections::{BinaryHeap, HashSet, VecDeque};
+use std::time::Instant;
+
+/// random graph with `vertexes` vertexes and about `edges` undirected edges
+fn make_random_graph(vertexes: usize, edges: usize) -> Graph {
+    let mut graph = Graph::new();
+    let mut rng = SeededRng::new(1);
+    for i in 0..vertexes {
+        graph.add_vertex(&i.to_string(), 0., 0., 0.);
+    }
+    let ids: Vec<String> = (0..vertexes).map(|i| i.to_string()).collect();
+    for _ in 0..edges {
+        let source = rng.next_index(vertexes);
+        let target = rng.next_index(vertexes);
+        graph.add_edge(&ids[source], &ids[target], 1. + rng.next_f64() as f32);
+    }
+    graph
+}
+
+fn time<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    println!(
+        "{:<28}{:>10.1} ms",
+        name,
+        start.elapsed().as_secs_f64() * 1000.
+    );
+    result
+}
+
+/// compare the HashMap adjacency with the csr snapshot on a 1M edge graph
+fn bench() {
+    let graph = time("build 200k / 1M graph", || {
+        make_random_graph(200_000, 1_000_000)
+    });
+    let len = graph.get_vertexes_len();
+    let csr = time("build csr snapshot", || graph.snapshot());
+    time("cached csr snapshot", || graph.snapshot());
+
+    let hash_sum = time("scan hashmap neighbors", || {
+        (0..len)
+            .map(|v| graph.get_neighbors(v).iter().sum::<usize>())
+            .sum::<usize>()
+    });
+    let csr_sum = time("scan csr neighbors", || {
+        (0..len)
+            .map(|v| csr.out_neighbors(v).iter().sum::<usize>())
+            .sum::<usize>()
+    });
+    assert_eq!(hash_sum, csr_sum);
+
+    let hash_bfs = time("bfs hashmap", || {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from(vec![0]);
+        visited.insert(0);
+        while let Some(v) = queue.pop_front() {
+            for u in graph.get_neighbors(v) {
+                if visited.insert(u) {
+                    queue.push_back(u);
+                }
+            }
+        }
+        visited.len()
+    });
+    let csr_bfs = time("bfs csr", || bfs::make_bfs(&csr, 0).len());
+    assert_eq!(hash_bfs, csr_bfs);
+
+    let hash_dist = time("dijkstra hashmap", || {
+        let mut dist = vec![f32::INFINITY; len];
+        dist[0] = 0.;
+        let mut heap = BinaryHeap::from(vec![State { dist: 0., index: 0 }]);
+        while let Some(State { dist: d, index: v }) = heap.pop() {
+            if d > dist[v] {
+                continue;
+            }
+            for (u, weight) in graph.neighbor_weights(v) {
+                let next = d + weight;
+                if next < dist[*u] {
+                    dist[*u] = next;
+                    heap.push(State {
+                        dist: next,
+                        index: *u,
+                    });
+                }
+            }
+        }
+        dist
+    });
+    time("sssp (cached snapshot)", || sssp::make_sssp(&graph, 0));
+    let csr_dist = time("dijkstra csr", || sssp::make_dijkstra(&csr, &[0]));
+    assert!(hash_dist
+        .iter()
+        .zip(&csr_dist.dist)
+        .all(|(a, b)| a == b || (a - b).abs() < 1e-3));
+
+    let mut graph = graph;
+    time("remove 200 vertexes", || {
+        for i in 0..200 {
+            graph.remove_vertex(&(i * 997).to_string());
+        }
+    });
+}
+
 fn main() {
     // get args from command line
     let args: Vec<String> = std::env::args().collect();
@@ -7,5 +118,7 @@ fn main() {
         println!("hello world");
     } else if arg == "run" {
         println!("run the program");
+    } else if arg == "bench" {
+        bench();
     }
 }